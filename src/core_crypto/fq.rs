@@ -0,0 +1,268 @@
+use super::{
+    modulus::{BarrettBackend, NativeModulusBackend},
+    num::UnsignedIntegerDoubled,
+};
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Mul, Neg, Sub},
+    sync::Arc,
+};
+
+/// Element of prime field F_q.
+///
+/// Elements hold a shared reference to modulus context and arithmetic uses barrett routines of
+/// `NativeModulusBackend`. Arithmetic between elements of different moduli panics.
+#[derive(Clone)]
+pub struct Fq<Scalar: UnsignedIntegerDoubled> {
+    value: Scalar,
+    context: Arc<NativeModulusBackend<Scalar>>,
+}
+
+impl<Scalar: UnsignedIntegerDoubled> Fq<Scalar> {
+    /// Returns `value (mod q)` as element of F_q
+    pub fn new(value: Scalar, context: &Arc<NativeModulusBackend<Scalar>>) -> Fq<Scalar> {
+        Fq {
+            value: context.reduce(value),
+            context: context.clone(),
+        }
+    }
+
+    pub fn zero(context: &Arc<NativeModulusBackend<Scalar>>) -> Fq<Scalar> {
+        Fq::new(Scalar::zero(), context)
+    }
+
+    pub fn one(context: &Arc<NativeModulusBackend<Scalar>>) -> Fq<Scalar> {
+        Fq::new(Scalar::one(), context)
+    }
+
+    /// Returns representative in [0, q)
+    pub fn value(&self) -> Scalar {
+        self.value
+    }
+
+    pub fn modulus(&self) -> Scalar {
+        self.context.modulus()
+    }
+
+    pub fn context(&self) -> &Arc<NativeModulusBackend<Scalar>> {
+        &self.context
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == Scalar::zero()
+    }
+
+    /// Returns self^e using binary exponentiation
+    pub fn pow(&self, mut e: Scalar) -> Fq<Scalar> {
+        let mut base = self.value;
+        let mut out = Scalar::one();
+        while e > Scalar::zero() {
+            if e & Scalar::one() == Scalar::one() {
+                out = self.context.mul_mod_fast(out, base);
+            }
+            base = self.context.mul_mod_fast(base, base);
+            e = e >> 1usize;
+        }
+        self.with_value(out)
+    }
+
+    /// Returns self^{-1}, or None if self is zero.
+    ///
+    /// Uses Fermat's little theorem, thus modulus must be prime.
+    pub fn inv(&self) -> Option<Fq<Scalar>> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.pow(self.modulus() - Scalar::one() - Scalar::one()))
+        }
+    }
+
+    fn with_value(&self, value: Scalar) -> Fq<Scalar> {
+        Fq {
+            value,
+            context: self.context.clone(),
+        }
+    }
+
+    fn assert_same_modulus(&self, other: &Fq<Scalar>) {
+        assert!(
+            Arc::ptr_eq(&self.context, &other.context) || self.modulus() == other.modulus(),
+            "Elements of F_{} and F_{} are incompatible",
+            self.modulus(),
+            other.modulus()
+        );
+    }
+}
+
+/// Implements `$trait` for all combinations of owned and borrowed operands using
+/// `$backend_fn` of `BarrettBackend`
+macro_rules! impl_binary_op {
+    ($trait:ident, $fn:ident, $backend_fn:ident) => {
+        impl<Scalar: UnsignedIntegerDoubled> $trait<&Fq<Scalar>> for &Fq<Scalar> {
+            type Output = Fq<Scalar>;
+
+            fn $fn(self, rhs: &Fq<Scalar>) -> Fq<Scalar> {
+                self.assert_same_modulus(rhs);
+                self.with_value(self.context.$backend_fn(self.value, rhs.value))
+            }
+        }
+
+        impl<Scalar: UnsignedIntegerDoubled> $trait<Fq<Scalar>> for &Fq<Scalar> {
+            type Output = Fq<Scalar>;
+
+            fn $fn(self, rhs: Fq<Scalar>) -> Fq<Scalar> {
+                self.$fn(&rhs)
+            }
+        }
+
+        impl<Scalar: UnsignedIntegerDoubled> $trait<&Fq<Scalar>> for Fq<Scalar> {
+            type Output = Fq<Scalar>;
+
+            fn $fn(self, rhs: &Fq<Scalar>) -> Fq<Scalar> {
+                (&self).$fn(rhs)
+            }
+        }
+
+        impl<Scalar: UnsignedIntegerDoubled> $trait<Fq<Scalar>> for Fq<Scalar> {
+            type Output = Fq<Scalar>;
+
+            fn $fn(self, rhs: Fq<Scalar>) -> Fq<Scalar> {
+                (&self).$fn(&rhs)
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, add_mod_fast);
+impl_binary_op!(Sub, sub, sub_mod_fast);
+impl_binary_op!(Mul, mul, mul_mod_fast);
+
+impl<Scalar: UnsignedIntegerDoubled> Neg for &Fq<Scalar> {
+    type Output = Fq<Scalar>;
+
+    fn neg(self) -> Fq<Scalar> {
+        self.with_value(self.context.sub_mod_fast(Scalar::zero(), self.value))
+    }
+}
+
+impl<Scalar: UnsignedIntegerDoubled> Neg for Fq<Scalar> {
+    type Output = Fq<Scalar>;
+
+    fn neg(self) -> Fq<Scalar> {
+        -&self
+    }
+}
+
+impl<Scalar: UnsignedIntegerDoubled> PartialEq for Fq<Scalar> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.modulus() == other.modulus()
+    }
+}
+
+impl<Scalar: UnsignedIntegerDoubled> Eq for Fq<Scalar> {}
+
+impl<Scalar: UnsignedIntegerDoubled> Display for Fq<Scalar> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<Scalar: UnsignedIntegerDoubled> Debug for Fq<Scalar> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_crypto::modulus::ModulusBackendConfig;
+    use rand::{thread_rng, Rng};
+
+    const PRIME_60_BITS: u64 = 1152921504606748673;
+    const PRIME_KYBER: u32 = 3329;
+    const K: usize = 1000;
+
+    #[test]
+    fn fq_arithmetic_works() {
+        let p = PRIME_60_BITS;
+        let context = Arc::new(NativeModulusBackend::initialise(p));
+        let mut rng = thread_rng();
+
+        let zero = Fq::zero(&context);
+        let one = Fq::one(&context);
+        for _ in 0..K {
+            let a_u64 = rng.gen::<u64>();
+            let b_u64 = rng.gen::<u64>();
+            let a = Fq::new(a_u64, &context);
+            let b = Fq::new(b_u64, &context);
+            let (a_u64, b_u64) = ((a_u64 % p) as u128, (b_u64 % p) as u128);
+            let p_u128 = p as u128;
+
+            assert_eq!((&a + &b).value() as u128, (a_u64 + b_u64) % p_u128);
+            assert_eq!((&a - &b).value() as u128, (a_u64 + p_u128 - b_u64) % p_u128);
+            assert_eq!((&a * &b).value() as u128, (a_u64 * b_u64) % p_u128);
+            assert_eq!((-&a).value() as u128, (p_u128 - a_u64) % p_u128);
+
+            assert_eq!(&a + &zero, a);
+            assert_eq!(&a * &one, a);
+            assert_eq!(&a + -&a, zero);
+            assert_eq!(a.clone() - b.clone() + b, a);
+
+            let a_inv = a.inv();
+            if a.is_zero() {
+                assert!(a_inv.is_none());
+            } else {
+                assert_eq!(a * a_inv.unwrap(), one);
+            }
+        }
+
+        // a^{p-1} = 1 and a^p = a
+        let a = Fq::new(rng.gen::<u64>(), &context);
+        assert_eq!(a.pow(p - 1), one);
+        assert_eq!(a.pow(p), a);
+        assert_eq!(a.pow(0), one);
+        assert_eq!(a.pow(3), &a * &a * &a);
+
+        assert!(zero.inv().is_none());
+        assert_eq!(format!("{}", Fq::new(p + 5, &context)), "5");
+        assert_eq!(
+            format!("{:?}", Fq::new(5, &context)),
+            format!("5 (mod {p})")
+        );
+    }
+
+    #[test]
+    fn fq_u32_works() {
+        let p = PRIME_KYBER;
+        let context = Arc::new(NativeModulusBackend::initialise(p));
+        let one = Fq::one(&context);
+        for a in 1..p {
+            let a = Fq::new(a, &context);
+            assert_eq!(&a * a.inv().unwrap(), one);
+        }
+    }
+
+    #[test]
+    fn fq_equality_compares_moduli() {
+        let context_a = Arc::new(NativeModulusBackend::initialise(PRIME_60_BITS));
+        let context_b = Arc::new(NativeModulusBackend::initialise(PRIME_60_BITS));
+        let context_c = Arc::new(NativeModulusBackend::initialise(1125899906826241));
+
+        // separate contexts with same modulus are compatible
+        assert_eq!(Fq::new(5, &context_a), Fq::new(5, &context_b));
+        assert_eq!(
+            Fq::new(5, &context_a) + Fq::new(5, &context_b),
+            Fq::new(10, &context_a)
+        );
+        assert_ne!(Fq::new(5, &context_a), Fq::new(5, &context_c));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fq_mixed_moduli_panics() {
+        let context_a = Arc::new(NativeModulusBackend::initialise(PRIME_60_BITS));
+        let context_b = Arc::new(NativeModulusBackend::initialise(1125899906826241));
+        let _ = Fq::new(5, &context_a) * Fq::new(5, &context_b);
+    }
+}
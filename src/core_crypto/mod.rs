@@ -1,4 +1,6 @@
-pub(crate) mod modulus;
-pub(crate) mod ntt;
-pub(crate) mod num;
-pub(crate) mod prime;
+pub mod fq;
+pub mod modulus;
+pub mod ntt;
+pub mod num;
+pub mod params;
+pub mod prime;
@@ -0,0 +1,382 @@
+use super::{
+    BarrettBackend, ModulusBackendConfig, ModulusVecBackend, NativeModulusBackend, SimdLevel,
+    SimdModulusBackend, SpecialFormModulusBackend,
+};
+use rand::{thread_rng, Rng};
+use std::{
+    fmt::Display,
+    fs::OpenOptions,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Implementations selectable by [ModulusBackend]
+///
+/// Montgomery and Shoup multiplication are not offered. [ModulusBackend] takes and returns values
+/// in normal space, which costs Montgomery an extra multiplication per product, and Shoup only pays
+/// off when Shoup's representation of a constant is precomputed once and reused, which this
+/// interface cannot express. Use `MontgomeryBackend` and `ShoupBackend` of `NativeModulusBackend`
+/// directly in these cases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModulusBackendKind {
+    /// `NativeModulusBackend` with barrett reduction
+    Barrett,
+    /// `SpecialFormModulusBackend`, only for moduli of special form
+    SpecialForm,
+    /// `SimdModulusBackend`, only if CPU supports AVX2 or AVX-512
+    Simd,
+}
+
+impl ModulusBackendKind {
+    pub const ALL: [ModulusBackendKind; 3] = [
+        ModulusBackendKind::Barrett,
+        ModulusBackendKind::SpecialForm,
+        ModulusBackendKind::Simd,
+    ];
+}
+
+impl Display for ModulusBackendKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl FromStr for ModulusBackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ModulusBackendKind::ALL
+            .into_iter()
+            .find(|kind| kind.to_string() == s)
+            .ok_or_else(|| format!("Unknown modulus backend {s}"))
+    }
+}
+
+/// Modulus backend for u64 moduli selected at runtime.
+///
+/// `initialise` micro-benchmarks every [ModulusBackendKind] supported for the modulus on the
+/// current CPU and picks the fastest. Use `with_kind` to pick one explicitly and
+/// `initialise_with_cache` to skip tuning on later runs.
+pub enum ModulusBackend {
+    Barrett(NativeModulusBackend<u64>),
+    SpecialForm(SpecialFormModulusBackend),
+    Simd(SimdModulusBackend),
+}
+
+/// Calls `$op` on backend wrapped by `$self`
+macro_rules! delegate {
+    ($self:ident, $op:ident($($arg:expr),*)) => {
+        match $self {
+            ModulusBackend::Barrett(backend) => backend.$op($($arg),*),
+            ModulusBackend::SpecialForm(backend) => backend.$op($($arg),*),
+            ModulusBackend::Simd(backend) => backend.$op($($arg),*),
+        }
+    };
+}
+
+impl ModulusBackend {
+    /// Returns backend of `kind` for `modulus`, if `kind` supports the modulus on current CPU.
+    ///
+    /// All kinds except `SpecialForm` require modulus of at most 60 bits.
+    pub fn with_kind(modulus: u64, kind: ModulusBackendKind) -> Option<ModulusBackend> {
+        let is_native = modulus >> 60 == 0;
+        match kind {
+            ModulusBackendKind::Barrett if is_native => Some(ModulusBackend::Barrett(
+                NativeModulusBackend::initialise(modulus),
+            )),
+            ModulusBackendKind::SpecialForm => {
+                SpecialFormModulusBackend::try_initialise(modulus).map(ModulusBackend::SpecialForm)
+            }
+            ModulusBackendKind::Simd if is_native && SimdLevel::detect() != SimdLevel::Portable => {
+                Some(ModulusBackend::Simd(SimdModulusBackend::initialise(
+                    modulus,
+                )))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> ModulusBackendKind {
+        match self {
+            ModulusBackend::Barrett(_) => ModulusBackendKind::Barrett,
+            ModulusBackend::SpecialForm(_) => ModulusBackendKind::SpecialForm,
+            ModulusBackend::Simd(_) => ModulusBackendKind::Simd,
+        }
+    }
+
+    /// Returns time taken by representative workload (vector multiplication, multiplication by
+    /// scalar, fused multiply-add and addition) on vectors of length 1024. Takes minimum across
+    /// a few rounds to filter out noise.
+    pub fn benchmark(&self) -> Duration {
+        const N: usize = 1024;
+        const ROUNDS: usize = 8;
+
+        let q = self.modulus();
+        let mut rng = thread_rng();
+        let mut random_vec = || (0..N).map(|_| rng.gen::<u64>() % q).collect::<Vec<_>>();
+        let a = random_vec();
+        let b = random_vec();
+        let c = random_vec();
+
+        (0..ROUNDS)
+            .map(|_| {
+                let mut out = a.clone();
+                let start = Instant::now();
+                self.mul_mod_vec(&mut out, &b);
+                self.scalar_mul_mod_vec(&mut out, c[0]);
+                self.fma_mod_vec(&mut out, &b, &c);
+                self.add_mod_vec(&mut out, &a);
+                let elapsed = start.elapsed();
+                std::hint::black_box(out);
+                elapsed
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// Returns backend for `modulus` with the least `benchmark` time.
+    ///
+    /// Panics if no backend supports the modulus, that is if it exceeds 60 bits and is not of
+    /// special form.
+    pub fn tune(modulus: u64) -> ModulusBackend {
+        ModulusBackendKind::ALL
+            .into_iter()
+            .filter_map(|kind| ModulusBackend::with_kind(modulus, kind))
+            .map(|backend| (backend.benchmark(), backend))
+            .min_by_key(|(elapsed, _)| *elapsed)
+            .map(|(_, backend)| backend)
+            .unwrap_or_else(|| panic!("No modulus backend supports modulus {modulus}"))
+    }
+
+    /// Same as `initialise` but reads the tuning result for `modulus` on current CPU from cache
+    /// file at `path`, if present. Otherwise tunes and appends the result to the cache.
+    ///
+    /// Each line of the cache file is of form `<modulus> <cpu> <kind>`. Lines that fail to parse
+    /// are ignored.
+    pub fn initialise_with_cache<P: AsRef<Path>>(
+        modulus: u64,
+        path: P,
+    ) -> io::Result<ModulusBackend> {
+        let cpu = cpu_signature();
+
+        match OpenOptions::new().read(true).open(path.as_ref()) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line?;
+                    let mut parts = line.split_whitespace();
+                    let (Some(q), Some(line_cpu), Some(kind), None) =
+                        (parts.next(), parts.next(), parts.next(), parts.next())
+                    else {
+                        continue;
+                    };
+                    if q.parse() != Ok(modulus) || line_cpu != cpu {
+                        continue;
+                    }
+                    if let Some(backend) = kind
+                        .parse()
+                        .ok()
+                        .and_then(|kind| ModulusBackend::with_kind(modulus, kind))
+                    {
+                        return Ok(backend);
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let backend = ModulusBackend::tune(modulus);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.as_ref())?;
+        writeln!(file, "{modulus} {cpu} {}", backend.kind())?;
+        Ok(backend)
+    }
+
+    pub fn modulus(&self) -> u64 {
+        match self {
+            ModulusBackend::Barrett(backend) => BarrettBackend::modulus(backend),
+            ModulusBackend::SpecialForm(backend) => backend.modulus(),
+            ModulusBackend::Simd(backend) => BarrettBackend::modulus(backend.native()),
+        }
+    }
+}
+
+/// Identifies CPU features that affect tuning results
+fn cpu_signature() -> String {
+    format!("{}-{:?}", std::env::consts::ARCH, SimdLevel::detect())
+}
+
+impl ModulusBackendConfig<u64> for ModulusBackend {
+    /// Tunes for the fastest backend. See `ModulusBackend::tune`.
+    fn initialise(modulus: u64) -> Self {
+        ModulusBackend::tune(modulus)
+    }
+}
+
+impl ModulusVecBackend<u64> for ModulusBackend {
+    fn add_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        delegate!(self, add_mod_vec(a, b))
+    }
+
+    fn sub_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        delegate!(self, sub_mod_vec(a, b))
+    }
+
+    fn mul_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        delegate!(self, mul_mod_vec(a, b))
+    }
+
+    fn neg_mod_vec(&self, a: &mut [u64]) {
+        delegate!(self, neg_mod_vec(a))
+    }
+
+    fn scalar_mul_mod_vec(&self, a: &mut [u64], b: u64) {
+        delegate!(self, scalar_mul_mod_vec(a, b))
+    }
+
+    fn fma_mod_vec(&self, a: &mut [u64], b: &[u64], c: &[u64]) {
+        delegate!(self, fma_mod_vec(a, b, c))
+    }
+
+    fn fms_mod_vec(&self, a: &mut [u64], b: &[u64], c: &[u64]) {
+        delegate!(self, fms_mod_vec(a, b, c))
+    }
+
+    fn inner_product_mod(&self, a: &[u64], b: &[u64]) -> u64 {
+        delegate!(self, inner_product_mod(a, b))
+    }
+
+    fn reduce_vec(&self, a: &mut [u64]) {
+        delegate!(self, reduce_vec(a))
+    }
+
+    fn reduce_doubled_vec(&self, a: &mut [u64], b: &[u128]) {
+        delegate!(self, reduce_doubled_vec(a, b))
+    }
+
+    fn reduce_i64_vec(&self, a: &mut [u64], b: &[i64]) {
+        delegate!(self, reduce_i64_vec(a, b))
+    }
+
+    fn to_centered_i64_vec(&self, a: &mut [i64], b: &[u64]) {
+        delegate!(self, to_centered_i64_vec(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_crypto::modulus::GOLDILOCKS_PRIME;
+
+    const PRIME_60_BITS: u64 = 1152921504606748673;
+    const PRIME_50_BITS: u64 = 1125899906826241;
+    const K: usize = 1000;
+
+    #[test]
+    fn modulus_backend_kinds_match_native() {
+        let mut rng = thread_rng();
+        for p in [PRIME_60_BITS, PRIME_50_BITS] {
+            let native = NativeModulusBackend::initialise(p);
+            let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+            let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+            let c = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+
+            for kind in ModulusBackendKind::ALL {
+                let Some(backend) = ModulusBackend::with_kind(p, kind) else {
+                    continue;
+                };
+                assert_eq!(backend.kind(), kind);
+                assert_eq!(backend.modulus(), p);
+
+                macro_rules! check {
+                    ($a:expr, $op:ident($($arg:expr),*)) => {
+                        let mut expected = $a.to_vec();
+                        native.$op(&mut expected, $($arg),*);
+                        let mut out = $a.to_vec();
+                        backend.$op(&mut out, $($arg),*);
+                        assert_eq!(out, expected, "{kind}: {} mismatch", stringify!($op));
+                    };
+                }
+                check!(a, add_mod_vec(&b));
+                check!(a, sub_mod_vec(&b));
+                check!(a, mul_mod_vec(&b));
+                check!(a, neg_mod_vec());
+                check!(a, scalar_mul_mod_vec(b[0]));
+                check!(a, fma_mod_vec(&b, &c));
+                check!(a, fms_mod_vec(&b, &c));
+                assert_eq!(
+                    backend.inner_product_mod(&a, &b),
+                    native.inner_product_mod(&a, &b)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn modulus_backend_tuning_works() {
+        let backend = ModulusBackend::initialise(PRIME_60_BITS);
+        assert_eq!(backend.modulus(), PRIME_60_BITS);
+
+        // only special form backend supports goldilocks
+        let backend = ModulusBackend::initialise(GOLDILOCKS_PRIME);
+        assert_eq!(backend.kind(), ModulusBackendKind::SpecialForm);
+
+        // not of special form
+        assert!(ModulusBackend::with_kind(1000000007, ModulusBackendKind::SpecialForm).is_none());
+        assert!(ModulusBackend::with_kind(GOLDILOCKS_PRIME, ModulusBackendKind::Barrett).is_none());
+
+        for kind in ModulusBackendKind::ALL {
+            assert_eq!(kind.to_string().parse::<ModulusBackendKind>(), Ok(kind));
+        }
+    }
+
+    #[test]
+    fn modulus_backend_cache_works() {
+        let path = std::env::temp_dir().join(format!(
+            "gauss-modulus-backend-cache-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        // tunes and writes to cache
+        let backend = ModulusBackend::initialise_with_cache(PRIME_60_BITS, &path).unwrap();
+        let cache = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            cache,
+            format!("{PRIME_60_BITS} {} {}\n", cpu_signature(), backend.kind())
+        );
+
+        // reads from cache, which we overwrite with a kind other than the tuned one to check that
+        // tuning is skipped
+        let cached_kind = if backend.kind() == ModulusBackendKind::Barrett {
+            ModulusBackendKind::SpecialForm
+        } else {
+            ModulusBackendKind::Barrett
+        };
+        std::fs::write(
+            &path,
+            format!(
+                "malformed line\n{PRIME_60_BITS} {} {cached_kind}\n",
+                cpu_signature()
+            ),
+        )
+        .unwrap();
+        let backend = ModulusBackend::initialise_with_cache(PRIME_60_BITS, &path).unwrap();
+        assert_eq!(backend.kind(), cached_kind);
+
+        // cache entry of another modulus is appended
+        let backend = ModulusBackend::initialise_with_cache(PRIME_50_BITS, &path).unwrap();
+        let cache = std::fs::read_to_string(&path).unwrap();
+        assert!(cache.ends_with(&format!(
+            "{PRIME_50_BITS} {} {}\n",
+            cpu_signature(),
+            backend.kind()
+        )));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
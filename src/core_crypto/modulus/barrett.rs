@@ -1,20 +1,38 @@
 use super::UnsignedInteger;
-use num_traits::AsPrimitive;
+use num_traits::{AsPrimitive, NumCast};
 
+/// Modular arithmetic with barrett reduction.
+///
+/// `*_fast` routines take inputs in [0, q) and output fully reduced values. Lazy routines (`*_lazy`)
+/// take and return values in [0, 2q) (or return values in [0, 4q) for `*_lazy_4q`, of which
+/// `mul_mod_lazy_4q` also accepts [0, 4q)) so that callers can chain operations without paying for a
+/// full reduction each time. Range contracts are checked in debug builds. Use `reduce_*` routines to
+/// reduce back to [0, q).
 pub trait BarrettBackend<Scalar, ScalarDoubled>
 where
-    Scalar: UnsignedInteger + AsPrimitive<ScalarDoubled> + AsPrimitive<u128> + 'static,
-    u128: AsPrimitive<Scalar>,
+    Scalar: UnsignedInteger + AsPrimitive<ScalarDoubled> + 'static,
     ScalarDoubled: UnsignedInteger + AsPrimitive<Scalar> + 'static,
 {
     /// Precomputes modulus specific barrett constant.
     /// We set \alpha = n + 3. Thus \mu = 2^{2*n+3}/modulus
+    ///
+    /// \mu < 2^{n+4} must fit in `Scalar`, hence modulus must be at most `Scalar::BITS - 4` bits.
     fn precompute_alpha_and_barrett_constant(modulus: Scalar) -> (usize, Scalar) {
         //TODO (Jay): Move barrett pre-compute in its own trait (like MontgomeryBackendConfig)
-        let modulus_bits = Scalar::BITS - modulus.leading_zeros();
+        let modulus_bits = (Scalar::BITS - modulus.leading_zeros()) as usize;
+        assert!(
+            modulus_bits <= Scalar::BITS as usize - 4,
+            "Modulus {modulus} has more than {} bits",
+            Scalar::BITS - 4
+        );
 
-        let mu = (1u128 << (modulus_bits * 2 + 3)) / <Scalar as AsPrimitive<u128>>::as_(modulus);
-        ((modulus_bits + 3) as usize, mu.as_())
+        let mu = (ScalarDoubled::one() << (modulus_bits * 2 + 3)) / modulus.as_();
+        (modulus_bits + 3, mu.as_())
+    }
+
+    /// Precomputes `floor(2^{BITS} / modulus)` used to reduce arbitrary `Scalar`s
+    fn precompute_word_barrett_constant(modulus: Scalar) -> Scalar {
+        ((ScalarDoubled::one() << (Scalar::BITS as usize)) / modulus.as_()).as_()
     }
 
     fn modulus(&self) -> Scalar;
@@ -25,6 +43,9 @@ where
 
     fn barrett_alpha(&self) -> usize;
 
+    /// `floor(2^{BITS} / modulus)`
+    fn word_barrett_constant(&self) -> Scalar;
+
     fn add_mod_fast(&self, a: Scalar, b: Scalar) -> Scalar {
         debug_assert!(
             a < self.modulus(),
@@ -107,4 +128,179 @@ where
 
         res
     }
+
+    /// Lazy modular addition. Given a, b in [0, 2q) outputs a + b (mod q) in [0, 2q)
+    fn add_mod_lazy(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        debug_assert!(a < q_twice, "Input {a} >= (2q){q_twice}");
+        debug_assert!(b < q_twice, "Input {b} >= (2q){q_twice}");
+
+        let mut c = a + b;
+        if c >= q_twice {
+            c -= q_twice;
+        }
+        c
+    }
+
+    /// Lazy modular subtraction. Given a, b in [0, 2q) outputs a - b (mod q) in [0, 2q)
+    fn sub_mod_lazy(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        debug_assert!(a < q_twice, "Input {a} >= (2q){q_twice}");
+        debug_assert!(b < q_twice, "Input {b} >= (2q){q_twice}");
+
+        let mut c = a + q_twice - b;
+        if c >= q_twice {
+            c -= q_twice;
+        }
+        c
+    }
+
+    /// Given a, b in [0, 2q) outputs a + b (mod q) in [0, 4q) without any reduction
+    fn add_mod_lazy_4q(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        debug_assert!(a < q_twice, "Input {a} >= (2q){q_twice}");
+        debug_assert!(b < q_twice, "Input {b} >= (2q){q_twice}");
+
+        a + b
+    }
+
+    /// Given a, b in [0, 2q) outputs a - b (mod q) in [0, 4q) without any reduction
+    fn sub_mod_lazy_4q(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        debug_assert!(a < q_twice, "Input {a} >= (2q){q_twice}");
+        debug_assert!(b < q_twice, "Input {b} >= (2q){q_twice}");
+
+        a + q_twice - b
+    }
+
+    /// Lazy barrett modular multiplication. Given a, b in [0, 2q) outputs ab (mod q) in [0, 2q).
+    ///
+    /// Same as `mul_mod_fast` except that the final conditional subtraction is skipped. Since
+    /// estimated quotient is off by at most 1, output is in [0, 2q).
+    fn mul_mod_lazy(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        debug_assert!(a < q_twice, "Input {a} >= (2q){q_twice}");
+        debug_assert!(b < q_twice, "Input {b} >= (2q){q_twice}");
+
+        let ab = <Scalar as AsPrimitive<ScalarDoubled>>::as_(a)
+            * <Scalar as AsPrimitive<ScalarDoubled>>::as_(b);
+        let tmp = ab >> (self.modulus_bits() - 2);
+        let q = (tmp * self.barrett_constant().as_()) >> (self.barrett_alpha() + 2);
+        (ab - q * self.modulus().as_()).as_()
+    }
+
+    /// Lazy barrett modular multiplication. Given a, b in [0, 4q) outputs ab (mod q) in [0, 4q).
+    ///
+    /// Uses the same \mu (i.e. \alpha = n + 3) as `mul_mod_lazy` but with \beta = -1. For ab < 16q^2
+    /// error of estimated quotient is < ab/2^{2n+3} + 2^{n-1}/q + 1 < 2 + 1 + 1, thus output is in
+    /// [0, 4q). \beta = -1 (instead of -2) keeps (ab / 2^{n-1}) * \mu < 2^{2n+8} within `ScalarDoubled`
+    /// for moduli of `Scalar::BITS - 4` bits.
+    fn mul_mod_lazy_4q(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        let q_four = q_twice + q_twice;
+        debug_assert!(a < q_four, "Input {a} >= (4q){q_four}");
+        debug_assert!(b < q_four, "Input {b} >= (4q){q_four}");
+
+        let ab = <Scalar as AsPrimitive<ScalarDoubled>>::as_(a)
+            * <Scalar as AsPrimitive<ScalarDoubled>>::as_(b);
+        let tmp = ab >> (self.modulus_bits() - 1);
+        let q = (tmp * self.barrett_constant().as_()) >> (self.barrett_alpha() + 1);
+        (ab - q * self.modulus().as_()).as_()
+    }
+
+    /// Reduces a in [0, 2q) to [0, q)
+    fn reduce_2q_to_q(&self, a: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        debug_assert!(a < q_twice, "Input {a} >= (2q){q_twice}");
+
+        if a >= self.modulus() {
+            a - self.modulus()
+        } else {
+            a
+        }
+    }
+
+    /// Reduces a in [0, 4q) to [0, 2q)
+    fn reduce_4q_to_2q(&self, a: Scalar) -> Scalar {
+        let q_twice = self.modulus() + self.modulus();
+        debug_assert!(
+            a < q_twice + q_twice,
+            "Input {a} >= (4q){}",
+            q_twice + q_twice
+        );
+
+        if a >= q_twice {
+            a - q_twice
+        } else {
+            a
+        }
+    }
+
+    /// Reduces a in [0, 4q) to [0, q)
+    fn reduce_4q_to_q(&self, a: Scalar) -> Scalar {
+        self.reduce_2q_to_q(self.reduce_4q_to_2q(a))
+    }
+
+    /// Reduces arbitrary `a` (i.e. a < 2^{BITS}) to [0, q)
+    ///
+    /// Estimates quotient as `(a * floor(2^{BITS}/q)) / 2^{BITS}` which is off by at most 1.
+    fn reduce(&self, a: Scalar) -> Scalar {
+        let k: Scalar = ((<Scalar as AsPrimitive<ScalarDoubled>>::as_(a)
+            * self.word_barrett_constant().as_())
+            >> (Scalar::BITS as usize))
+            .as_();
+        let mut r = a.wrapping_sub(&k.wrapping_mul(&self.modulus()));
+        if r >= self.modulus() {
+            r -= self.modulus();
+        }
+        r
+    }
+
+    /// Reduces arbitrary `a` in `ScalarDoubled` (i.e. a < 2^{2*BITS}) to [0, q)
+    ///
+    /// Splits a = a_hi * 2^{BITS} + a_lo and outputs a_hi * (2^{BITS} mod q) + a_lo (mod q)
+    fn reduce_doubled(&self, a: ScalarDoubled) -> Scalar {
+        let a_hi: Scalar = (a >> (Scalar::BITS as usize)).as_();
+        let a_lo: Scalar = a.as_();
+
+        // 2^{BITS} (mod q)
+        let r_modq = Scalar::zero()
+            .wrapping_sub(&self.word_barrett_constant().wrapping_mul(&self.modulus()));
+
+        self.add_mod_fast(
+            self.mul_mod_fast(self.reduce(a_hi), r_modq),
+            self.reduce(a_lo),
+        )
+    }
+
+    /// Maps signed `a` to [0, q)
+    fn reduce_i64(&self, a: i64) -> Scalar {
+        let a_abs: ScalarDoubled =
+            NumCast::from(a.unsigned_abs()).expect("ScalarDoubled cannot hold i64");
+        let r = self.reduce_doubled(a_abs);
+        if a < 0 && r != Scalar::zero() {
+            self.modulus() - r
+        } else {
+            r
+        }
+    }
+
+    /// Maps `a` in [0, q) to its centered representative in (-q/2, q/2]
+    fn to_centered_i64(&self, a: Scalar) -> i64 {
+        debug_assert!(
+            a < self.modulus(),
+            "Input {a} >= (modulus){}",
+            self.modulus()
+        );
+
+        let q_half = self.modulus() >> 1usize;
+        if a > q_half {
+            -(self.modulus() - a)
+                .to_i64()
+                .expect("centered representative does not fit in i64")
+        } else {
+            a.to_i64()
+                .expect("centered representative does not fit in i64")
+        }
+    }
 }
@@ -0,0 +1,498 @@
+use super::{
+    BarrettBackend, ModulusBackendConfig, ModulusVecBackend, MontgomeryBackend, MontgomeryScalar,
+    NativeModulusBackend,
+};
+use crate::{
+    core_crypto::num::{UnsignedInteger, UnsignedIntegerDoubled},
+    utils::FastModularInverse,
+};
+use itertools::izip;
+use num_traits::{AsPrimitive, NumCast};
+use std::hint::black_box;
+
+/// Returns `a - q` if `a >= q` and `a` otherwise without branching on `a`.
+///
+/// Requires a < 2q and 2q <= 2^{BITS-1}, so that the top bit of `a - q` (wrapping) is set iff `a < q`.
+#[inline]
+fn reduce_once_ct<Scalar: UnsignedInteger>(a: Scalar, q: Scalar) -> Scalar {
+    let d = a.wrapping_sub(&q);
+    // all ones if a < q, zero otherwise
+    let mask = black_box(Scalar::zero().wrapping_sub(&(d >> (Scalar::BITS as usize - 1))));
+    d.wrapping_add(&(q & mask))
+}
+
+/// Returns `if_true` if `condition` is all ones and `if_false` if `condition` is zero
+#[inline]
+fn select_ct<Scalar: UnsignedInteger>(
+    condition: Scalar,
+    if_true: Scalar,
+    if_false: Scalar,
+) -> Scalar {
+    if_false ^ ((if_true ^ if_false) & condition)
+}
+
+/// Constant time variants of barrett and montgomery routines.
+///
+/// Routines in `BarrettBackend` and `MontgomeryBackend` use data dependent branches (for ex, `if c >= modulus`)
+/// which leak secret values through timing. Routines here replace them with branchless conditional subtraction.
+///
+/// Note that arithmetic on `U256` has data dependent fast paths, thus only u32 and u64 scalars are
+/// constant time.
+pub trait ConstantTimeBackend<Scalar, ScalarDoubled>:
+    BarrettBackend<Scalar, ScalarDoubled> + MontgomeryBackend<Scalar, ScalarDoubled>
+where
+    Scalar: UnsignedInteger + AsPrimitive<ScalarDoubled> + 'static,
+    ScalarDoubled: UnsignedInteger + AsPrimitive<Scalar> + 'static,
+{
+    fn add_mod_ct(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q = BarrettBackend::modulus(self);
+        debug_assert!(a < q, "Input {a} >= (modulus){q}");
+        debug_assert!(b < q, "Input {b} >= (modulus){q}");
+
+        reduce_once_ct(a + b, q)
+    }
+
+    fn sub_mod_ct(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q = BarrettBackend::modulus(self);
+        debug_assert!(a < q, "Input {a} >= (modulus){q}");
+        debug_assert!(b < q, "Input {b} >= (modulus){q}");
+
+        reduce_once_ct(a + q - b, q)
+    }
+
+    fn neg_mod_ct(&self, a: Scalar) -> Scalar {
+        let q = BarrettBackend::modulus(self);
+        debug_assert!(a < q, "Input {a} >= (modulus){q}");
+
+        // q - 0 = q reduces to 0
+        reduce_once_ct(q - a, q)
+    }
+
+    /// Same as `BarrettBackend::mul_mod_fast` with branchless final subtraction
+    fn mul_mod_ct(&self, a: Scalar, b: Scalar) -> Scalar {
+        let q = BarrettBackend::modulus(self);
+        debug_assert!(a < q + q, "Input {a} >= (2q){}", q + q);
+        debug_assert!(b < q + q, "Input {b} >= (2q){}", q + q);
+
+        let ab = <Scalar as AsPrimitive<ScalarDoubled>>::as_(a)
+            * <Scalar as AsPrimitive<ScalarDoubled>>::as_(b);
+        let tmp = ab >> (self.modulus_bits() - 2);
+        let quotient = (tmp * self.barrett_constant().as_()) >> (self.barrett_alpha() + 2);
+        reduce_once_ct((ab - quotient * q.as_()).as_(), q)
+    }
+
+    /// Same as `BarrettBackend::reduce` with branchless final subtraction
+    fn reduce_ct(&self, a: Scalar) -> Scalar {
+        let q = BarrettBackend::modulus(self);
+        let k: Scalar = ((<Scalar as AsPrimitive<ScalarDoubled>>::as_(a)
+            * self.word_barrett_constant().as_())
+            >> (Scalar::BITS as usize))
+            .as_();
+        reduce_once_ct(a.wrapping_sub(&k.wrapping_mul(&q)), q)
+    }
+
+    /// Same as `BarrettBackend::reduce_doubled` with branchless final subtractions
+    fn reduce_doubled_ct(&self, a: ScalarDoubled) -> Scalar {
+        let q = BarrettBackend::modulus(self);
+        let a_hi: Scalar = (a >> (Scalar::BITS as usize)).as_();
+        let a_lo: Scalar = a.as_();
+        let r_modq = Scalar::zero().wrapping_sub(&self.word_barrett_constant().wrapping_mul(&q));
+
+        self.add_mod_ct(
+            self.mul_mod_ct(self.reduce_ct(a_hi), r_modq),
+            self.reduce_ct(a_lo),
+        )
+    }
+
+    /// Same as `BarrettBackend::reduce_i64` without branching on sign of `a`
+    fn reduce_i64_ct(&self, a: i64) -> Scalar {
+        let a_abs: ScalarDoubled =
+            NumCast::from(a.unsigned_abs()).expect("ScalarDoubled cannot hold i64");
+        let r = self.reduce_doubled_ct(a_abs);
+
+        let is_negative: Scalar = NumCast::from((a as u64) >> 63).unwrap();
+        select_ct(
+            Scalar::zero().wrapping_sub(&is_negative),
+            self.neg_mod_ct(r),
+            r,
+        )
+    }
+
+    /// Same as `BarrettBackend::to_centered_i64` without branching on `a`
+    fn to_centered_i64_ct(&self, a: Scalar) -> i64 {
+        let q = BarrettBackend::modulus(self);
+        debug_assert!(a < q, "Input {a} >= (modulus){q}");
+
+        let a = a.to_i64().expect("Input does not fit in i64");
+        let q = q.to_i64().expect("Modulus does not fit in i64");
+        // all ones if a > q/2
+        let mask = black_box(((q >> 1) - a) >> 63);
+        a - (q & mask)
+    }
+
+    /// Same as `MontgomeryBackend::mont_mul` with branchless final subtraction
+    fn mont_mul_ct(
+        &self,
+        a: MontgomeryScalar<Scalar>,
+        b: MontgomeryScalar<Scalar>,
+    ) -> MontgomeryScalar<Scalar> {
+        let n = MontgomeryBackend::modulus(self);
+        MontgomeryScalar(reduce_once_ct(self.mont_mul_lazy(a, b).0, n))
+    }
+
+    fn mont_add_ct(
+        &self,
+        a: MontgomeryScalar<Scalar>,
+        b: MontgomeryScalar<Scalar>,
+    ) -> MontgomeryScalar<Scalar> {
+        MontgomeryScalar(self.add_mod_ct(a.0, b.0))
+    }
+
+    fn mont_sub_ct(
+        &self,
+        a: MontgomeryScalar<Scalar>,
+        b: MontgomeryScalar<Scalar>,
+    ) -> MontgomeryScalar<Scalar> {
+        MontgomeryScalar(self.sub_mod_ct(a.0, b.0))
+    }
+}
+
+impl<Scalar> ConstantTimeBackend<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar> where
+    Scalar: UnsignedIntegerDoubled
+{
+}
+
+/// `ModulusVecBackend` whose routines run in time independent of input values.
+///
+/// Uses routines of [ConstantTimeBackend] of the wrapped `NativeModulusBackend`.
+pub struct ConstantTimeModulusBackend<Scalar: UnsignedIntegerDoubled> {
+    native: NativeModulusBackend<Scalar>,
+}
+
+impl<Scalar: UnsignedIntegerDoubled> ConstantTimeModulusBackend<Scalar> {
+    pub fn native(&self) -> &NativeModulusBackend<Scalar> {
+        &self.native
+    }
+}
+
+impl<Scalar> ModulusBackendConfig<Scalar> for ConstantTimeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled + FastModularInverse,
+    Scalar::Doubled: FastModularInverse,
+{
+    fn initialise(modulus: Scalar) -> Self {
+        ConstantTimeModulusBackend {
+            native: NativeModulusBackend::initialise(modulus),
+        }
+    }
+}
+
+impl<Scalar> ModulusVecBackend<Scalar> for ConstantTimeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled,
+{
+    fn add_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.native.add_mod_ct(*a0, *b0);
+        })
+    }
+
+    fn sub_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.native.sub_mod_ct(*a0, *b0);
+        })
+    }
+
+    fn mul_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.native.mul_mod_ct(*a0, *b0);
+        })
+    }
+
+    fn neg_mod_vec(&self, a: &mut [Scalar]) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.native.neg_mod_ct(*a0);
+        })
+    }
+
+    fn scalar_mul_mod_vec(&self, a: &mut [Scalar], b: Scalar) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.native.mul_mod_ct(*a0, b);
+        })
+    }
+
+    fn fma_mod_vec(&self, a: &mut [Scalar], b: &[Scalar], c: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter(), c.iter()).for_each(|(a0, b0, c0)| {
+            *a0 = self
+                .native
+                .add_mod_ct(*a0, self.native.mul_mod_ct(*b0, *c0));
+        })
+    }
+
+    fn fms_mod_vec(&self, a: &mut [Scalar], b: &[Scalar], c: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter(), c.iter()).for_each(|(a0, b0, c0)| {
+            *a0 = self
+                .native
+                .sub_mod_ct(*a0, self.native.mul_mod_ct(*b0, *c0));
+        })
+    }
+
+    /// Unlike `NativeModulusBackend` reduces after every product since reduction of doubled accumulator
+    /// with `%` is not constant time.
+    fn inner_product_mod(&self, a: &[Scalar], b: &[Scalar]) -> Scalar {
+        izip!(a.iter(), b.iter()).fold(Scalar::zero(), |acc, (a0, b0)| {
+            self.native
+                .add_mod_ct(acc, self.native.mul_mod_ct(*a0, *b0))
+        })
+    }
+
+    fn reduce_vec(&self, a: &mut [Scalar]) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.native.reduce_ct(*a0);
+        })
+    }
+
+    fn reduce_doubled_vec(&self, a: &mut [Scalar], b: &[Scalar::Doubled]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.native.reduce_doubled_ct(*b0);
+        })
+    }
+
+    fn reduce_i64_vec(&self, a: &mut [Scalar], b: &[i64]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.native.reduce_i64_ct(*b0);
+        })
+    }
+
+    fn to_centered_i64_vec(&self, a: &mut [i64], b: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.native.to_centered_i64_ct(*b0);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{seq::SliceRandom, thread_rng, Rng};
+    use std::time::Instant;
+
+    const PRIME_60_BITS: u64 = 1152921504606748673;
+    const K: usize = 1000;
+
+    #[test]
+    fn constant_time_modulus_backend_works() {
+        let p = PRIME_60_BITS;
+        let mut rng = thread_rng();
+        let native = NativeModulusBackend::initialise(p);
+        let ct = ConstantTimeModulusBackend::initialise(p);
+
+        for _ in 0..K {
+            let a = rng.gen::<u64>() % p;
+            let b = rng.gen::<u64>() % p;
+            assert_eq!(native.add_mod_ct(a, b), native.add_mod_fast(a, b));
+            assert_eq!(native.sub_mod_ct(a, b), native.sub_mod_fast(a, b));
+            assert_eq!(native.mul_mod_ct(a, b), native.mul_mod_fast(a, b));
+            assert_eq!(native.neg_mod_ct(a), (p - a) % p);
+
+            let a_mont = native.normal_to_mont_space(a);
+            let b_mont = native.normal_to_mont_space(b);
+            assert_eq!(
+                native.mont_to_normal(native.mont_mul_ct(a_mont, b_mont)),
+                native.mul_mod_fast(a, b)
+            );
+
+            let a = rng.gen::<u64>();
+            assert_eq!(native.reduce_ct(a), native.reduce(a));
+            let a = rng.gen::<u128>();
+            assert_eq!(native.reduce_doubled_ct(a), native.reduce_doubled(a));
+            let a = rng.gen::<i64>();
+            assert_eq!(native.reduce_i64_ct(a), native.reduce_i64(a));
+            let a = rng.gen::<u64>() % p;
+            assert_eq!(native.to_centered_i64_ct(a), native.to_centered_i64(a));
+        }
+        assert_eq!(native.neg_mod_ct(0), 0);
+        assert_eq!(native.reduce_i64_ct(0), 0);
+        assert_eq!(native.reduce_i64_ct(-(p as i64)), 0);
+
+        // vector routines match native backend
+        let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let c = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        macro_rules! check {
+            ($a:expr, $op:ident($($arg:expr),*)) => {
+                let mut expected = $a.to_vec();
+                native.$op(&mut expected, $($arg),*);
+                let mut out = $a.to_vec();
+                ct.$op(&mut out, $($arg),*);
+                assert_eq!(out, expected, "{} mismatch", stringify!($op));
+            };
+        }
+        check!(a, add_mod_vec(&b));
+        check!(a, sub_mod_vec(&b));
+        check!(a, mul_mod_vec(&b));
+        check!(a, neg_mod_vec());
+        check!(a, scalar_mul_mod_vec(b[0]));
+        check!(a, fma_mod_vec(&b, &c));
+        check!(a, fms_mod_vec(&b, &c));
+        assert_eq!(
+            ct.inner_product_mod(&a, &b),
+            native.inner_product_mod(&a, &b)
+        );
+    }
+
+    /// Welch's t-statistic of two samples
+    fn welch_t(x: &[f64], y: &[f64]) -> f64 {
+        let mean_var = |v: &[f64]| {
+            let mean = v.iter().sum::<f64>() / v.len() as f64;
+            let var = v.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (v.len() - 1) as f64;
+            (mean, var)
+        };
+        let (mx, vx) = mean_var(x);
+        let (my, vy) = mean_var(y);
+        (mx - my) / (vx / x.len() as f64 + vy / y.len() as f64).sqrt()
+    }
+
+    /// dudect style fixed-vs-random test.
+    ///
+    /// Times `op` on batches of inputs that are either all equal to a fixed value (class 0) or random
+    /// (class 1). Classes are interleaved randomly, measurements above a percentile are cropped, and
+    /// Welch's t-test is applied to the two classes. Returns max |t| across crop percentiles.
+    ///
+    /// - [Reference](https://eprint.iacr.org/2016/1069.pdf)
+    fn dudect<F: Fn(u64, u64) -> u64>(op: F, fixed: (u64, u64), p: u64) -> f64 {
+        const MEASUREMENTS: usize = 4000;
+        const BATCH: usize = 64;
+
+        let mut rng = thread_rng();
+        let mut classes = (0..MEASUREMENTS).map(|i| i & 1).collect::<Vec<_>>();
+        classes.shuffle(&mut rng);
+
+        let inputs = classes
+            .iter()
+            .map(|class| {
+                (0..BATCH)
+                    .map(|_| {
+                        if *class == 0 {
+                            fixed
+                        } else {
+                            (rng.gen::<u64>() % p, rng.gen::<u64>() % p)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut timings = [vec![], vec![]];
+        for (class, batch) in izip!(classes.iter(), inputs.iter()) {
+            let start = Instant::now();
+            let mut acc = 0u64;
+            for (a, b) in batch.iter() {
+                acc ^= op(black_box(*a), black_box(*b));
+            }
+            black_box(acc);
+            timings[*class].push(start.elapsed().as_nanos() as f64);
+        }
+
+        let mut sorted = timings.concat();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        [0.5, 0.75, 0.9]
+            .iter()
+            .map(|percentile| {
+                let threshold = sorted[(sorted.len() as f64 * percentile) as usize];
+                let cropped = timings
+                    .iter()
+                    .map(|t| {
+                        t.iter()
+                            .copied()
+                            .filter(|t| *t <= threshold)
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                welch_t(&cropped[0], &cropped[1]).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Conditional subtraction that is compiled to a branch. In optimised builds rustc turns
+    /// `if c >= q { c -= q }` of `*_fast` routines into a conditional move, thus they cannot serve as
+    /// positive controls of the timing-leak test.
+    #[inline(never)]
+    #[cold]
+    fn sub_cold(c: u64, q: u64) -> u64 {
+        c - q
+    }
+
+    /// Timing measurements are too noisy in debug builds or when tests run in parallel. Thus the
+    /// test is ignored by default, run it with
+    /// `cargo test --release -- --ignored --test-threads=1 constant_time_modulus_backend_has_no_timing_leak`
+    #[test]
+    #[ignore]
+    fn constant_time_modulus_backend_has_no_timing_leak() {
+        // |t| > 10 indicates leakage with overwhelming confidence
+        const T_THRESHOLD: f64 = 10.0;
+
+        let p = PRIME_60_BITS;
+        let native = NativeModulusBackend::initialise(p);
+
+        // Positive controls: conditional subtractions of `add_mod_fast` and `mont_mul` with a
+        // branch must be detected, otherwise measurements below are meaningless. Fixed inputs never
+        // (resp. always) take the branch, random inputs do half the time.
+        let add_mod_branchy = |a: u64, b: u64| {
+            let c = a + b;
+            if c >= p {
+                sub_cold(c, p)
+            } else {
+                c
+            }
+        };
+        let t = dudect(add_mod_branchy, (0, 0), p);
+        assert!(
+            t > T_THRESHOLD,
+            "Timing leak of branchy add_mod is not detected: |t| = {t}"
+        );
+
+        let mont_mul_branchy = |a: u64, b: u64| {
+            let c = native
+                .mont_mul_lazy(MontgomeryScalar(a), MontgomeryScalar(b))
+                .0;
+            if c >= p {
+                sub_cold(c, p)
+            } else {
+                c
+            }
+        };
+        let t = dudect(mont_mul_branchy, (0, 0), p);
+        assert!(
+            t > T_THRESHOLD,
+            "Timing leak of branchy mont_mul is not detected: |t| = {t}"
+        );
+
+        // fixed inputs never trigger the conditional subtraction, random inputs do half the time
+        let t = dudect(|a, b| native.add_mod_ct(a, b), (0, 0), p);
+        assert!(t < T_THRESHOLD, "add_mod_ct leaks timing: |t| = {t}");
+
+        let t = dudect(|a, b| native.sub_mod_ct(a, b), (0, 0), p);
+        assert!(t < T_THRESHOLD, "sub_mod_ct leaks timing: |t| = {t}");
+
+        let t = dudect(|a, b| native.mul_mod_ct(a, b), (1, 1), p);
+        assert!(
+            t < T_THRESHOLD,
+            "mul_mod_ct (barrett) leaks timing: |t| = {t}"
+        );
+
+        let t = dudect(
+            |a, b| {
+                let a = MontgomeryScalar(a);
+                let b = MontgomeryScalar(b);
+                native.mont_mul_ct(a, b).0
+            },
+            (1, 1),
+            p,
+        );
+        assert!(
+            t < T_THRESHOLD,
+            "mont_mul_ct (montgomery) leaks timing: |t| = {t}"
+        );
+    }
+}
@@ -1,14 +1,26 @@
-use super::num::UnsignedInteger;
+use super::num::{UnsignedInteger, UnsignedIntegerDoubled};
 
+mod backend;
 mod barrett;
+mod constant_time;
 mod montgomery;
 mod native_backend;
+mod plantard;
 mod shoup;
+mod simd;
+mod special_form;
 
+pub use backend::{ModulusBackend, ModulusBackendKind};
 pub use barrett::BarrettBackend;
-pub use montgomery::{MontgomeryBackend, MontgomeryBackendConfig};
+pub use constant_time::{ConstantTimeBackend, ConstantTimeModulusBackend};
+pub use montgomery::{
+    MontgomeryBackend, MontgomeryBackendConfig, MontgomeryScalar, MontgomeryVecBackend,
+};
 pub use native_backend::NativeModulusBackend;
-pub use shoup::ShoupRepresentationFq;
+pub use plantard::{PlantardBackend, PlantardBackendConfig};
+pub use shoup::{ShoupBackend, ShoupRepresentationFq};
+pub use simd::{SimdLevel, SimdModulusBackend};
+pub use special_form::{SpecialForm, SpecialFormModulusBackend, GOLDILOCKS_PRIME};
 
 pub trait ModulusBackendConfig<Scalar> {
     fn initialise(modulus: Scalar) -> Self;
@@ -16,9 +28,39 @@ pub trait ModulusBackendConfig<Scalar> {
 
 pub trait ModulusVecBackend<Scalar>
 where
-    Scalar: UnsignedInteger,
+    Scalar: UnsignedIntegerDoubled,
 {
     fn add_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]);
     fn sub_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]);
     fn mul_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]);
+
+    /// a_i = -a_i (mod q)
+    fn neg_mod_vec(&self, a: &mut [Scalar]);
+
+    /// a_i = a_i * b (mod q)
+    fn scalar_mul_mod_vec(&self, a: &mut [Scalar], b: Scalar);
+
+    /// a_i = a_i + b_i * c_i (mod q)
+    fn fma_mod_vec(&self, a: &mut [Scalar], b: &[Scalar], c: &[Scalar]);
+
+    /// a_i = a_i - b_i * c_i (mod q)
+    fn fms_mod_vec(&self, a: &mut [Scalar], b: &[Scalar], c: &[Scalar]);
+
+    /// Returns \sum a_i * b_i (mod q)
+    ///
+    /// Products are accumulated in `Scalar::Doubled` and only reduced when the accumulator
+    /// might overflow.
+    fn inner_product_mod(&self, a: &[Scalar], b: &[Scalar]) -> Scalar;
+
+    /// Reduces arbitrary a_i to [0, q)
+    fn reduce_vec(&self, a: &mut [Scalar]);
+
+    /// Sets a_i to b_i (mod q) where b_i are arbitrary `Scalar::Doubled`s
+    fn reduce_doubled_vec(&self, a: &mut [Scalar], b: &[Scalar::Doubled]);
+
+    /// Sets a_i to signed b_i mapped to [0, q)
+    fn reduce_i64_vec(&self, a: &mut [Scalar], b: &[i64]);
+
+    /// Sets a_i to centered representative of b_i in (-q/2, q/2]
+    fn to_centered_i64_vec(&self, a: &mut [i64], b: &[Scalar]);
 }
@@ -1,11 +1,12 @@
 use crate::{core_crypto::num::UnsignedInteger, utils::FastModularInverse};
+use itertools::izip;
 use num_traits::AsPrimitive;
 
 /// Wrapper around `MontgomeryScalar`s.
 ///
 /// This is just to prevent cross-arithmatic between Scalars in Montgomery space and Scalars in Normal space.
 #[derive(Clone, Copy)]
-pub struct MontgomeryScalar<Scalar: UnsignedInteger>(Scalar);
+pub struct MontgomeryScalar<Scalar: UnsignedInteger>(pub(super) Scalar);
 
 impl<Scalar: UnsignedInteger> std::fmt::Display for MontgomeryScalar<Scalar> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -146,3 +147,69 @@ where
         self.mont_mul(a, MontgomeryScalar(Scalar::one())).0
     }
 }
+
+/// Vector operations on scalars in Montgomery space.
+///
+/// Mirrors `ModulusVecBackend` for slices of `MontgomeryScalar`s so that pipelines that stay in
+/// Montgomery space across multiple multiplications convert in bulk only at the boundaries.
+///
+/// Lazy variants output values in [0, 2n). Since montgomery multiplication only requires
+/// `ab < n*r`, outputs of lazy variants can be fed back into `mont_mul_vec`/`mont_mul_lazy_vec`
+/// as long as `n < r/4`. Call `mont_reduce_vec` to bring them back to [0, n).
+pub trait MontgomeryVecBackend<Scalar, ScalarDoubled>:
+    MontgomeryBackend<Scalar, ScalarDoubled>
+where
+    Scalar: UnsignedInteger + AsPrimitive<ScalarDoubled> + 'static,
+    ScalarDoubled: UnsignedInteger + AsPrimitive<Scalar> + 'static,
+{
+    fn mont_add_vec(&self, a: &mut [MontgomeryScalar<Scalar>], b: &[MontgomeryScalar<Scalar>]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.mont_add(*a0, *b0);
+        })
+    }
+
+    fn mont_sub_vec(&self, a: &mut [MontgomeryScalar<Scalar>], b: &[MontgomeryScalar<Scalar>]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.mont_sub(*a0, *b0);
+        })
+    }
+
+    /// Outputs a_i = a_i * b_i * r^{-1} (mod n) in [0, n)
+    fn mont_mul_vec(&self, a: &mut [MontgomeryScalar<Scalar>], b: &[MontgomeryScalar<Scalar>]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.mont_mul(*a0, *b0);
+        })
+    }
+
+    /// Outputs a_i = a_i * b_i * r^{-1} (mod n) in [0, 2n)
+    fn mont_mul_lazy_vec(
+        &self,
+        a: &mut [MontgomeryScalar<Scalar>],
+        b: &[MontgomeryScalar<Scalar>],
+    ) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.mont_mul_lazy(*a0, *b0);
+        })
+    }
+
+    /// Reduces outputs of lazy variants from [0, 2n) to [0, n)
+    fn mont_reduce_vec(&self, a: &mut [MontgomeryScalar<Scalar>]) {
+        let n = self.modulus();
+        a.iter_mut().for_each(|a0| {
+            debug_assert!(a0.0 < n + n, "Input {a0} >= (2n){}", n + n);
+            if a0.0 >= n {
+                a0.0 -= n;
+            }
+        })
+    }
+
+    /// Transforms a vector of scalars in normal space to montgomery space
+    fn normal_to_mont_space_vec(&self, a: &[Scalar]) -> Vec<MontgomeryScalar<Scalar>> {
+        a.iter().map(|a0| self.normal_to_mont_space(*a0)).collect()
+    }
+
+    /// Transforms a vector of scalars in montgomery space to normal space
+    fn mont_to_normal_vec(&self, a: &[MontgomeryScalar<Scalar>]) -> Vec<Scalar> {
+        a.iter().map(|a0| self.mont_to_normal(*a0)).collect()
+    }
+}
@@ -1,47 +1,88 @@
 use super::{
     barrett::BarrettBackend,
-    montgomery::{MontgomeryBackend, MontgomeryBackendConfig},
+    montgomery::{MontgomeryBackend, MontgomeryBackendConfig, MontgomeryVecBackend},
+    plantard::{PlantardBackend, PlantardBackendConfig},
+    shoup::ShoupBackend,
     ModulusBackendConfig, ModulusVecBackend,
 };
+use crate::{core_crypto::num::UnsignedIntegerDoubled, utils::FastModularInverse};
 use itertools::izip;
+use num_traits::{AsPrimitive, Bounded, One, ToPrimitive, Zero};
 
-pub struct NativeModulusBackend {
-    modulus: u64,
-    barrett_constant: u64,
+/// Modulus backend for moduli that fit in a native `Scalar` (u32/u64/u128).
+///
+/// Intermediate products are computed in `Scalar::Doubled`.
+pub struct NativeModulusBackend<Scalar: UnsignedIntegerDoubled> {
+    modulus: Scalar,
+    barrett_constant: Scalar,
     barrett_alpha: usize,
+    word_barrett_constant: Scalar,
     modulus_bits: usize,
 
     /// Montgomery constant `n^{-1} (mod r)`
-    n_inv_modr_mont: u64,
+    n_inv_modr_mont: Scalar,
     /// Montgomery constant `r^2 (mod n)`
-    r_square_modn_mont: u64,
+    r_square_modn_mont: Scalar,
+
+    /// Plantard constant `q^{-1} (mod 2^{2W})`
+    q_inv_mod_r_square_plantard: Scalar::Doubled,
+    /// Plantard constant `-2^{2W} (mod q)`
+    minus_r_square_modq_plantard: Scalar,
 }
 
-impl ModulusBackendConfig<u64> for NativeModulusBackend {
-    fn initialise(modulus: u64) -> NativeModulusBackend {
-        let (alpha, mu) = <NativeModulusBackend>::precompute_alpha_and_barrett_constant(modulus);
-        let (n_inv_modr_mont, r_square_modn_mont) =
-            <NativeModulusBackend as MontgomeryBackendConfig<u64, u128>>::initialise(modulus);
+impl<Scalar> ModulusBackendConfig<Scalar> for NativeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled + FastModularInverse,
+    Scalar::Doubled: FastModularInverse,
+{
+    fn initialise(modulus: Scalar) -> NativeModulusBackend<Scalar> {
+        let (alpha, mu) = <NativeModulusBackend<Scalar> as BarrettBackend<
+            Scalar,
+            Scalar::Doubled,
+        >>::precompute_alpha_and_barrett_constant(modulus);
+        let word_barrett_constant = <NativeModulusBackend<Scalar> as BarrettBackend<
+            Scalar,
+            Scalar::Doubled,
+        >>::precompute_word_barrett_constant(modulus);
+        let (n_inv_modr_mont, r_square_modn_mont) = <NativeModulusBackend<Scalar> as MontgomeryBackendConfig<
+            Scalar,
+            Scalar::Doubled,
+        >>::initialise(modulus);
+        let (q_inv_mod_r_square_plantard, minus_r_square_modq_plantard) = <NativeModulusBackend<
+            Scalar,
+        > as PlantardBackendConfig<
+            Scalar,
+            Scalar::Doubled,
+        >>::initialise(
+            modulus
+        );
 
         NativeModulusBackend {
             modulus,
             barrett_alpha: alpha,
             barrett_constant: mu,
-            modulus_bits: 64 - modulus.leading_zeros() as usize,
+            word_barrett_constant,
+            modulus_bits: (Scalar::BITS - modulus.leading_zeros()) as usize,
 
             n_inv_modr_mont,
             r_square_modn_mont,
+
+            q_inv_mod_r_square_plantard,
+            minus_r_square_modq_plantard,
         }
     }
 }
 
-impl BarrettBackend<u64, u128> for NativeModulusBackend {
+impl<Scalar> BarrettBackend<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled,
+{
     #[inline]
     fn barrett_alpha(&self) -> usize {
         self.barrett_alpha
     }
     #[inline]
-    fn modulus(&self) -> u64 {
+    fn modulus(&self) -> Scalar {
         self.modulus
     }
     #[inline]
@@ -49,55 +90,203 @@ impl BarrettBackend<u64, u128> for NativeModulusBackend {
         self.modulus_bits
     }
     #[inline]
-    fn barrett_constant(&self) -> u64 {
+    fn barrett_constant(&self) -> Scalar {
         self.barrett_constant
     }
+    #[inline]
+    fn word_barrett_constant(&self) -> Scalar {
+        self.word_barrett_constant
+    }
 }
 
-impl ModulusVecBackend<u64> for NativeModulusBackend {
-    fn add_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+impl<Scalar> ModulusVecBackend<Scalar> for NativeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled,
+{
+    fn add_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]) {
         izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
             *a0 = self.add_mod_fast(*a0, *b0);
         })
     }
 
-    fn sub_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+    fn sub_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]) {
         izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
             *a0 = self.sub_mod_fast(*a0, *b0);
         })
     }
 
-    fn mul_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+    fn mul_mod_vec(&self, a: &mut [Scalar], b: &[Scalar]) {
         izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
             *a0 = self.mul_mod_fast(*a0, *b0);
         })
     }
+
+    fn neg_mod_vec(&self, a: &mut [Scalar]) {
+        a.iter_mut().for_each(|a0| {
+            debug_assert!(
+                *a0 < self.modulus,
+                "Input {a0} >= (modulus){}",
+                self.modulus
+            );
+            if *a0 != Scalar::zero() {
+                *a0 = self.modulus - *a0;
+            }
+        })
+    }
+
+    fn scalar_mul_mod_vec(&self, a: &mut [Scalar], b: Scalar) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.mul_mod_fast(*a0, b);
+        })
+    }
+
+    fn fma_mod_vec(&self, a: &mut [Scalar], b: &[Scalar], c: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter(), c.iter()).for_each(|(a0, b0, c0)| {
+            *a0 = self.add_mod_fast(*a0, self.mul_mod_fast(*b0, *c0));
+        })
+    }
+
+    fn fms_mod_vec(&self, a: &mut [Scalar], b: &[Scalar], c: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter(), c.iter()).for_each(|(a0, b0, c0)| {
+            *a0 = self.sub_mod_fast(*a0, self.mul_mod_fast(*b0, *c0));
+        })
+    }
+
+    fn inner_product_mod(&self, a: &[Scalar], b: &[Scalar]) -> Scalar {
+        debug_assert!(a.len() == b.len());
+
+        let q: Scalar::Doubled = self.modulus.as_();
+        let q_minus_one = q - Scalar::Doubled::one();
+
+        // No. of products (each < (q-1)^2) that can be added to a reduced accumulator (< q)
+        // without overflowing
+        let max_terms = ((Scalar::Doubled::max_value() - q) / (q_minus_one * q_minus_one))
+            .to_usize()
+            .unwrap_or(usize::MAX)
+            .max(1);
+
+        let mut acc = Scalar::Doubled::zero();
+        izip!(a.chunks(max_terms), b.chunks(max_terms)).for_each(|(a_chunk, b_chunk)| {
+            izip!(a_chunk.iter(), b_chunk.iter()).for_each(|(a0, b0)| {
+                debug_assert!(
+                    *a0 < self.modulus,
+                    "Input {a0} >= (modulus){}",
+                    self.modulus
+                );
+                debug_assert!(
+                    *b0 < self.modulus,
+                    "Input {b0} >= (modulus){}",
+                    self.modulus
+                );
+                acc += a0.as_() * b0.as_();
+            });
+            acc %= q;
+        });
+
+        acc.as_()
+    }
+
+    fn reduce_vec(&self, a: &mut [Scalar]) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.reduce(*a0);
+        })
+    }
+
+    fn reduce_doubled_vec(&self, a: &mut [Scalar], b: &[Scalar::Doubled]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.reduce_doubled(*b0);
+        })
+    }
+
+    fn reduce_i64_vec(&self, a: &mut [Scalar], b: &[i64]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.reduce_i64(*b0);
+        })
+    }
+
+    fn to_centered_i64_vec(&self, a: &mut [i64], b: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.to_centered_i64(*b0);
+        })
+    }
 }
 
-impl MontgomeryBackendConfig<u64, u128> for NativeModulusBackend {}
-impl MontgomeryBackend<u64, u128> for NativeModulusBackend {
+impl<Scalar> MontgomeryBackendConfig<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar> where
+    Scalar: UnsignedIntegerDoubled + FastModularInverse
+{
+}
+impl<Scalar> MontgomeryBackend<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled,
+{
     #[inline]
-    fn modulus(&self) -> u64 {
+    fn modulus(&self) -> Scalar {
         self.modulus
     }
 
     #[inline]
-    fn n_inverse_modr(&self) -> u64 {
+    fn n_inverse_modr(&self) -> Scalar {
         self.n_inv_modr_mont
     }
 
     #[inline]
-    fn r_square_modn(&self) -> u64 {
+    fn r_square_modn(&self) -> Scalar {
         self.r_square_modn_mont
     }
 }
+impl<Scalar> MontgomeryVecBackend<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar> where
+    Scalar: UnsignedIntegerDoubled
+{
+}
+
+impl<Scalar> PlantardBackendConfig<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled,
+    Scalar::Doubled: FastModularInverse,
+{
+}
+impl<Scalar> PlantardBackend<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled,
+{
+    #[inline]
+    fn modulus(&self) -> Scalar {
+        self.modulus
+    }
+
+    #[inline]
+    fn q_inverse_mod_r_square(&self) -> Scalar::Doubled {
+        self.q_inv_mod_r_square_plantard
+    }
+
+    #[inline]
+    fn minus_r_square_modq(&self) -> Scalar {
+        self.minus_r_square_modq_plantard
+    }
+}
+
+impl<Scalar> ShoupBackend<Scalar, Scalar::Doubled> for NativeModulusBackend<Scalar>
+where
+    Scalar: UnsignedIntegerDoubled,
+{
+    #[inline]
+    fn modulus(&self) -> Scalar {
+        self.modulus
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core_crypto::{modulus::ShoupRepresentationFq, num::U256};
     use rand::{thread_rng, Rng};
 
     const PRIME_60_BITS: u64 = 1152921504606748673;
+    const PRIME_KYBER: u32 = 3329;
+    const PRIME_DILITHIUM: u32 = 8380417;
+    const PRIME_100_BITS: u128 = 1267650600228229401496703205361;
+    const PRIME_124_BITS: u128 = 21267647932558653966460912964485513157;
+    const PRIME_125_BITS: u128 = 42535295865117307932921825928971026423;
 
     const K: usize = 1000;
 
@@ -105,7 +294,8 @@ mod tests {
     fn native_modulus_backend_works() {
         let p = PRIME_60_BITS;
         let mut rng = thread_rng();
-        let modulus_backend = <NativeModulusBackend as ModulusBackendConfig<u64>>::initialise(p);
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
         for _ in 0..K {
             // Case when a,b < p
             let a = rng.gen::<u64>() % p;
@@ -137,7 +327,8 @@ mod tests {
     fn native_modulus_montogomery_backend_works() {
         let p = PRIME_60_BITS;
         let mut rng = thread_rng();
-        let modulus_backend = <NativeModulusBackend as ModulusBackendConfig<u64>>::initialise(p);
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
         for _ in 0..1 {
             // a,b < p
             let a = rng.gen::<u64>() % p;
@@ -175,4 +366,532 @@ mod tests {
             assert_eq!(c, c_expected);
         }
     }
+
+    #[test]
+    fn native_modulus_backend_u32_works() {
+        let mut rng = thread_rng();
+        for p in [PRIME_KYBER, PRIME_DILITHIUM] {
+            let modulus_backend =
+                <NativeModulusBackend<u32> as ModulusBackendConfig<u32>>::initialise(p);
+            for _ in 0..K {
+                let a = rng.gen::<u32>() % p;
+                let b = rng.gen::<u32>() % p;
+
+                let c = modulus_backend.mul_mod_fast(a, b);
+                let c_expected = ((a as u64 * b as u64) % p as u64) as u32;
+                assert_eq!(c, c_expected);
+
+                let c = modulus_backend.add_mod_fast(a, b);
+                let c_expected = (a + b) % p;
+                assert_eq!(c, c_expected);
+
+                let c = modulus_backend.sub_mod_fast(a, b);
+                let c_expected = (a + p - b) % p;
+                assert_eq!(c, c_expected);
+
+                // montgomery multiplication
+                let a_mont = modulus_backend.normal_to_mont_space(a);
+                let b_mont = modulus_backend.normal_to_mont_space(b);
+                assert_eq!(a, modulus_backend.mont_to_normal(a_mont));
+                let c = modulus_backend.mont_to_normal(modulus_backend.mont_mul(a_mont, b_mont));
+                let c_expected = ((a as u64 * b as u64) % p as u64) as u32;
+                assert_eq!(c, c_expected);
+            }
+        }
+    }
+
+    #[test]
+    fn native_modulus_backend_u128_works() {
+        let mut rng = thread_rng();
+        for p in [PRIME_100_BITS, PRIME_124_BITS] {
+            let modulus_backend =
+                <NativeModulusBackend<u128> as ModulusBackendConfig<u128>>::initialise(p);
+            let mul_mod_expected =
+                |a: u128, b: u128| (U256::widening_mul(a, b) % U256::from_parts(0, p)).lo();
+            for _ in 0..K {
+                let a = rng.gen::<u128>() % p;
+                let b = rng.gen::<u128>() % p;
+
+                let c = modulus_backend.mul_mod_fast(a, b);
+                assert_eq!(c, mul_mod_expected(a, b));
+
+                let c = modulus_backend.add_mod_fast(a, b);
+                let c_expected = (a + b) % p;
+                assert_eq!(c, c_expected);
+
+                let c = modulus_backend.sub_mod_fast(a, b);
+                let c_expected = (a + p - b) % p;
+                assert_eq!(c, c_expected);
+
+                // montgomery multiplication
+                let a_mont = modulus_backend.normal_to_mont_space(a);
+                let b_mont = modulus_backend.normal_to_mont_space(b);
+                assert_eq!(a, modulus_backend.mont_to_normal(a_mont));
+                let c = modulus_backend.mont_to_normal(modulus_backend.mont_mul(a_mont, b_mont));
+                assert_eq!(c, mul_mod_expected(a, b));
+
+                // Case when a,b < 2p
+                let a = rng.gen_range(0..(2 * p));
+                let b = rng.gen_range(0..(2 * p));
+                let c = modulus_backend.mul_mod_fast(a, b);
+                assert_eq!(c, mul_mod_expected(a % p, b % p));
+            }
+        }
+    }
+
+    #[test]
+    fn native_modulus_backend_u128_barrett_constant_fits() {
+        // largest 124 bit prime, \mu = 2^{2*124+3}/p must not be truncated
+        let p = PRIME_124_BITS;
+        let modulus_backend =
+            <NativeModulusBackend<u128> as ModulusBackendConfig<u128>>::initialise(p);
+        assert_eq!(modulus_backend.modulus_bits(), 124);
+        let mu_expected = (U256::from(1u128) << 251usize) / U256::from(p);
+        assert_eq!(U256::from(modulus_backend.barrett_constant()), mu_expected);
+
+        let mut rng = thread_rng();
+        for _ in 0..K {
+            let a = rng.gen::<u128>() % p;
+            let b = rng.gen::<u128>() % p;
+            assert_eq!(
+                modulus_backend.mul_mod_fast(a, b),
+                (U256::widening_mul(a, b) % U256::from(p)).lo()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn native_modulus_backend_u128_rejects_125_bits() {
+        <NativeModulusBackend<u128> as ModulusBackendConfig<u128>>::initialise(PRIME_125_BITS);
+    }
+
+    #[test]
+    fn native_modulus_montgomery_vec_backend_works() {
+        let p = PRIME_60_BITS;
+        let mut rng = thread_rng();
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+
+        let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let a_mont = modulus_backend.normal_to_mont_space_vec(&a);
+        let b_mont = modulus_backend.normal_to_mont_space_vec(&b);
+        assert_eq!(a, modulus_backend.mont_to_normal_vec(&a_mont));
+
+        let mut c_mont = a_mont.clone();
+        modulus_backend.mont_mul_vec(&mut c_mont, &b_mont);
+        let c_expected = izip!(a.iter(), b.iter())
+            .map(|(a0, b0)| ((*a0 as u128 * *b0 as u128) % p as u128) as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(modulus_backend.mont_to_normal_vec(&c_mont), c_expected);
+
+        // chain lazy multiplications: a * b * b
+        let mut c_mont = a_mont.clone();
+        modulus_backend.mont_mul_lazy_vec(&mut c_mont, &b_mont);
+        modulus_backend.mont_mul_lazy_vec(&mut c_mont, &b_mont);
+        modulus_backend.mont_reduce_vec(&mut c_mont);
+        let c_expected = izip!(c_expected.iter(), b.iter())
+            .map(|(c0, b0)| ((*c0 as u128 * *b0 as u128) % p as u128) as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(modulus_backend.mont_to_normal_vec(&c_mont), c_expected);
+
+        let mut c_mont = a_mont.clone();
+        modulus_backend.mont_add_vec(&mut c_mont, &b_mont);
+        let c_expected = izip!(a.iter(), b.iter())
+            .map(|(a0, b0)| (a0 + b0) % p)
+            .collect::<Vec<_>>();
+        assert_eq!(modulus_backend.mont_to_normal_vec(&c_mont), c_expected);
+
+        let mut c_mont = a_mont.clone();
+        modulus_backend.mont_sub_vec(&mut c_mont, &b_mont);
+        let c_expected = izip!(a.iter(), b.iter())
+            .map(|(a0, b0)| (a0 + p - b0) % p)
+            .collect::<Vec<_>>();
+        assert_eq!(modulus_backend.mont_to_normal_vec(&c_mont), c_expected);
+    }
+
+    #[test]
+    fn native_modulus_shoup_backend_works() {
+        let p = PRIME_60_BITS;
+        let mut rng = thread_rng();
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+        for _ in 0..K {
+            // a is arbitrary, b < p
+            let a = rng.gen::<u64>();
+            let b = rng.gen::<u64>() % p;
+            let b_shoup = b.shoup_representation_fq(p);
+
+            let c_expected = ((a as u128 * b as u128) % p as u128) as u64;
+            let c = modulus_backend.shoup_mul_lazy(a, b, b_shoup);
+            assert!(c < 2 * p);
+            assert_eq!(c % p, c_expected);
+            let c = modulus_backend.shoup_mul(a, b, b_shoup);
+            assert_eq!(c, c_expected);
+        }
+
+        let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b_shoup = b
+            .iter()
+            .map(|b0| b0.shoup_representation_fq(p))
+            .collect::<Vec<_>>();
+
+        // multiply by fixed vector
+        let mut c = a.clone();
+        modulus_backend.shoup_mul_vec(&mut c, &b, &b_shoup);
+        let c_expected = izip!(a.iter(), b.iter())
+            .map(|(a0, b0)| ((*a0 as u128 * *b0 as u128) % p as u128) as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(c, c_expected);
+
+        let mut c = a.clone();
+        modulus_backend.shoup_mul_lazy_vec(&mut c, &b, &b_shoup);
+        assert!(c.iter().all(|c0| *c0 < 2 * p));
+        assert_eq!(c.iter().map(|c0| c0 % p).collect::<Vec<_>>(), c_expected);
+
+        // multiply by fixed scalar
+        let mut c = a.clone();
+        modulus_backend.shoup_scalar_mul_vec(&mut c, b[0], b_shoup[0]);
+        let c_expected = a
+            .iter()
+            .map(|a0| ((*a0 as u128 * b[0] as u128) % p as u128) as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(c, c_expected);
+
+        let mut c = a.clone();
+        modulus_backend.shoup_scalar_mul_lazy_vec(&mut c, b[0], b_shoup[0]);
+        assert_eq!(c.iter().map(|c0| c0 % p).collect::<Vec<_>>(), c_expected);
+
+        // u32 and u128 scalars
+        let modulus_backend =
+            <NativeModulusBackend<u32> as ModulusBackendConfig<u32>>::initialise(PRIME_DILITHIUM);
+        let b = rng.gen::<u32>() % PRIME_DILITHIUM;
+        let a = rng.gen::<u32>();
+        let c = modulus_backend.shoup_mul(a, b, b.shoup_representation_fq(PRIME_DILITHIUM));
+        assert_eq!(c, ((a as u64 * b as u64) % PRIME_DILITHIUM as u64) as u32);
+
+        let modulus_backend =
+            <NativeModulusBackend<u128> as ModulusBackendConfig<u128>>::initialise(PRIME_124_BITS);
+        let b = rng.gen::<u128>() % PRIME_124_BITS;
+        let a = rng.gen::<u128>();
+        let c = modulus_backend.shoup_mul(a, b, b.shoup_representation_fq(PRIME_124_BITS));
+        assert_eq!(
+            c,
+            (U256::widening_mul(a, b) % U256::from_parts(0, PRIME_124_BITS)).lo()
+        );
+    }
+
+    #[test]
+    fn native_modulus_plantard_backend_works() {
+        let p = PRIME_60_BITS;
+        let mut rng = thread_rng();
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+        for (a, b) in [(0, 0), (p - 1, p - 1), (1, p - 1), (p - 1, 1)]
+            .into_iter()
+            .chain((0..K).map(|_| (rng.gen::<u64>() % p, rng.gen::<u64>() % p)))
+        {
+            let b_plantard = modulus_backend.plantard_representation(b);
+            let c = modulus_backend.plantard_mul(a, b_plantard);
+            assert_eq!(c, modulus_backend.mul_mod_fast(a, b));
+        }
+
+        let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b_plantard = modulus_backend.plantard_representation_vec(&b);
+
+        // multiply by fixed vector
+        let mut c = a.clone();
+        modulus_backend.plantard_mul_vec(&mut c, &b_plantard);
+        let mut c_expected = a.clone();
+        modulus_backend.mul_mod_vec(&mut c_expected, &b);
+        assert_eq!(c, c_expected);
+
+        // multiply by fixed scalar
+        let mut c = a.clone();
+        modulus_backend.plantard_scalar_mul_vec(&mut c, b_plantard[0]);
+        let mut c_expected = a.clone();
+        modulus_backend.scalar_mul_mod_vec(&mut c_expected, b[0]);
+        assert_eq!(c, c_expected);
+
+        // u32 and u128 scalars
+        for p in [PRIME_KYBER, PRIME_DILITHIUM] {
+            let modulus_backend =
+                <NativeModulusBackend<u32> as ModulusBackendConfig<u32>>::initialise(p);
+            for _ in 0..K {
+                let a = rng.gen::<u32>() % p;
+                let b = rng.gen::<u32>() % p;
+                let c = modulus_backend.plantard_mul(a, modulus_backend.plantard_representation(b));
+                assert_eq!(c, modulus_backend.mul_mod_fast(a, b));
+            }
+        }
+
+        for p in [PRIME_100_BITS, PRIME_124_BITS] {
+            let modulus_backend =
+                <NativeModulusBackend<u128> as ModulusBackendConfig<u128>>::initialise(p);
+            for _ in 0..K {
+                let a = rng.gen::<u128>() % p;
+                let b = rng.gen::<u128>() % p;
+                let c = modulus_backend.plantard_mul(a, modulus_backend.plantard_representation(b));
+                assert_eq!(c, modulus_backend.mul_mod_fast(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn native_modulus_vec_backend_works() {
+        let p = PRIME_60_BITS;
+        let mut rng = thread_rng();
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+
+        let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let c = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let mul_mod = |a: u64, b: u64| ((a as u128 * b as u128) % p as u128) as u64;
+
+        let mut out = a.clone();
+        modulus_backend.neg_mod_vec(&mut out);
+        let out_expected = a.iter().map(|a0| (p - a0) % p).collect::<Vec<_>>();
+        assert_eq!(out, out_expected);
+
+        let mut out = vec![0u64; 4];
+        modulus_backend.neg_mod_vec(&mut out);
+        assert_eq!(out, vec![0u64; 4]);
+
+        let mut out = a.clone();
+        modulus_backend.scalar_mul_mod_vec(&mut out, b[0]);
+        let out_expected = a.iter().map(|a0| mul_mod(*a0, b[0])).collect::<Vec<_>>();
+        assert_eq!(out, out_expected);
+
+        let mut out = a.clone();
+        modulus_backend.fma_mod_vec(&mut out, &b, &c);
+        let out_expected = izip!(a.iter(), b.iter(), c.iter())
+            .map(|(a0, b0, c0)| ((*a0 as u128 + mul_mod(*b0, *c0) as u128) % p as u128) as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(out, out_expected);
+
+        let mut out = a.clone();
+        modulus_backend.fms_mod_vec(&mut out, &b, &c);
+        let out_expected = izip!(a.iter(), b.iter(), c.iter())
+            .map(|(a0, b0, c0)| {
+                ((*a0 as u128 + p as u128 - mul_mod(*b0, *c0) as u128) % p as u128) as u64
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(out, out_expected);
+
+        // K > no. of 120 bit products that fit in u128 accumulator
+        let out = modulus_backend.inner_product_mod(&a, &b);
+        let out_expected = izip!(a.iter(), b.iter()).fold(0u128, |acc, (a0, b0)| {
+            (acc + mul_mod(*a0, *b0) as u128) % p as u128
+        });
+        assert_eq!(out as u128, out_expected);
+
+        // maximum possible products
+        let a = vec![p - 1; K];
+        let out = modulus_backend.inner_product_mod(&a, &a);
+        let out_expected = (K as u128 * mul_mod(p - 1, p - 1) as u128) % p as u128;
+        assert_eq!(out as u128, out_expected);
+
+        // u32 inner product
+        let modulus_backend =
+            <NativeModulusBackend<u32> as ModulusBackendConfig<u32>>::initialise(PRIME_KYBER);
+        let a = (0..K)
+            .map(|_| rng.gen::<u32>() % PRIME_KYBER)
+            .collect::<Vec<_>>();
+        let b = (0..K)
+            .map(|_| rng.gen::<u32>() % PRIME_KYBER)
+            .collect::<Vec<_>>();
+        let out = modulus_backend.inner_product_mod(&a, &b);
+        let out_expected = izip!(a.iter(), b.iter())
+            .fold(0u128, |acc, (a0, b0)| acc + (*a0 as u128 * *b0 as u128))
+            % PRIME_KYBER as u128;
+        assert_eq!(out as u128, out_expected);
+    }
+
+    #[test]
+    fn native_modulus_lazy_backend_works() {
+        let p = PRIME_60_BITS;
+        let mut rng = thread_rng();
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+        let mul_mod = |a: u64, b: u64| ((a as u128 * b as u128) % p as u128) as u64;
+        for _ in 0..K {
+            let a = rng.gen_range(0..(2 * p));
+            let b = rng.gen_range(0..(2 * p));
+
+            let c = modulus_backend.add_mod_lazy(a, b);
+            assert!(c < 2 * p);
+            assert_eq!(c % p, (a + b) % p);
+
+            let c = modulus_backend.sub_mod_lazy(a, b);
+            assert!(c < 2 * p);
+            assert_eq!(c % p, (a + 2 * p - b) % p);
+
+            let c = modulus_backend.add_mod_lazy_4q(a, b);
+            assert!(c < 4 * p);
+            assert_eq!(modulus_backend.reduce_4q_to_q(c), (a + b) % p);
+            assert_eq!(modulus_backend.reduce_4q_to_2q(c) % p, (a + b) % p);
+
+            let c = modulus_backend.sub_mod_lazy_4q(a, b);
+            assert!(c < 4 * p);
+            assert_eq!(modulus_backend.reduce_4q_to_q(c), (a + 2 * p - b) % p);
+
+            let c = modulus_backend.mul_mod_lazy(a, b);
+            assert!(c < 2 * p);
+            assert_eq!(modulus_backend.reduce_2q_to_q(c), mul_mod(a % p, b % p));
+
+            // chain (a * b + a) * b - a without intermediate full reductions
+            let c = modulus_backend.mul_mod_lazy(a, b);
+            let c = modulus_backend.add_mod_lazy(c, a);
+            let c = modulus_backend.mul_mod_lazy(c, b);
+            let c = modulus_backend.sub_mod_lazy(c, a);
+            let c_expected = (mul_mod((mul_mod(a % p, b % p) + a % p) % p, b % p) + p - a % p) % p;
+            assert_eq!(modulus_backend.reduce_2q_to_q(c), c_expected);
+        }
+    }
+
+    #[test]
+    fn native_modulus_mul_mod_lazy_4q_works() {
+        let mut rng = thread_rng();
+
+        let p = PRIME_60_BITS;
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+        let mul_mod = |a: u64, b: u64| ((a as u128 * b as u128) % p as u128) as u64;
+        let edges = [(4 * p - 1, 4 * p - 1), (4 * p - 1, 0), (2 * p, 2 * p - 1)];
+        for (a, b) in (0..K)
+            .map(|_| (rng.gen_range(0..(4 * p)), rng.gen_range(0..(4 * p))))
+            .chain(edges)
+        {
+            let c = modulus_backend.mul_mod_lazy_4q(a, b);
+            assert!(c < 4 * p);
+            assert_eq!(modulus_backend.reduce_4q_to_q(c), mul_mod(a % p, b % p));
+
+            // output of lazy_4q routines can be fed back without intermediate reduction
+            let d = modulus_backend.add_mod_lazy_4q(
+                modulus_backend.reduce_4q_to_2q(c),
+                modulus_backend.mul_mod_lazy(a % p, b % p),
+            );
+            let d = modulus_backend.mul_mod_lazy_4q(d, c);
+            assert!(d < 4 * p);
+            assert_eq!(
+                modulus_backend.reduce_4q_to_q(d),
+                mul_mod(2 * mul_mod(a % p, b % p) % p, c % p)
+            );
+        }
+
+        // largest modulus supported by barrett
+        let p = PRIME_124_BITS;
+        let modulus_backend =
+            <NativeModulusBackend<u128> as ModulusBackendConfig<u128>>::initialise(p);
+        for _ in 0..K {
+            let a = rng.gen_range(0..(4 * p));
+            let b = rng.gen_range(0..(4 * p));
+            let c = modulus_backend.mul_mod_lazy_4q(a, b);
+            assert!(c < 4 * p);
+            let c_expected = (U256::widening_mul(a % p, b % p) % U256::from(p)).lo();
+            assert_eq!(modulus_backend.reduce_4q_to_q(c), c_expected);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn native_modulus_mul_mod_lazy_4q_rejects_out_of_range_input() {
+        let p = PRIME_60_BITS;
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+        modulus_backend.mul_mod_lazy_4q(4 * p, 1);
+    }
+
+    #[test]
+    fn native_modulus_reduction_works() {
+        let mut rng = thread_rng();
+
+        let p = PRIME_60_BITS;
+        let modulus_backend =
+            <NativeModulusBackend<u64> as ModulusBackendConfig<u64>>::initialise(p);
+        for _ in 0..K {
+            let a = rng.gen::<u64>();
+            assert_eq!(modulus_backend.reduce(a), a % p);
+
+            let a = rng.gen::<u128>();
+            assert_eq!(modulus_backend.reduce_doubled(a), (a % p as u128) as u64);
+
+            let a = rng.gen::<i64>();
+            let a_modp = a.rem_euclid(p as i64) as u64;
+            assert_eq!(modulus_backend.reduce_i64(a), a_modp);
+
+            let centered = modulus_backend.to_centered_i64(a_modp);
+            assert!(centered > -((p / 2) as i64) - 1 && centered <= (p / 2) as i64);
+            assert_eq!(modulus_backend.reduce_i64(centered), a_modp);
+        }
+
+        // edge cases
+        for a in [0u64, 1, p - 1, p, p + 1, 2 * p, u64::MAX] {
+            assert_eq!(modulus_backend.reduce(a), a % p);
+        }
+        for a in [0u128, p as u128, u128::MAX, (p as u128) * (p as u128)] {
+            assert_eq!(modulus_backend.reduce_doubled(a), (a % p as u128) as u64);
+        }
+        for a in [0i64, 1, -1, i64::MIN, i64::MAX, -(p as i64)] {
+            assert_eq!(
+                modulus_backend.reduce_i64(a),
+                (a as i128).rem_euclid(p as i128) as u64
+            );
+        }
+        assert_eq!(modulus_backend.to_centered_i64(p / 2), (p / 2) as i64);
+        assert_eq!(
+            modulus_backend.to_centered_i64(p / 2 + 1),
+            -((p / 2) as i64)
+        );
+        assert_eq!(modulus_backend.to_centered_i64(p - 1), -1);
+
+        // vector forms
+        let a = (0..K).map(|_| rng.gen::<i64>()).collect::<Vec<_>>();
+        let mut a_modp = vec![0u64; K];
+        modulus_backend.reduce_i64_vec(&mut a_modp, &a);
+        let mut a_centered = vec![0i64; K];
+        modulus_backend.to_centered_i64_vec(&mut a_centered, &a_modp);
+        izip!(a.iter(), a_modp.iter(), a_centered.iter()).for_each(|(a0, a0_modp, a0_centered)| {
+            assert_eq!(*a0_modp, a0.rem_euclid(p as i64) as u64);
+            assert_eq!(a0_centered.rem_euclid(p as i64), a0.rem_euclid(p as i64));
+        });
+
+        let b = (0..K).map(|_| rng.gen::<u128>()).collect::<Vec<_>>();
+        let mut b_modp = vec![0u64; K];
+        modulus_backend.reduce_doubled_vec(&mut b_modp, &b);
+        assert_eq!(
+            b_modp,
+            b.iter()
+                .map(|b0| (b0 % p as u128) as u64)
+                .collect::<Vec<_>>()
+        );
+
+        let mut c = (0..K).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+        let c_expected = c.iter().map(|c0| c0 % p).collect::<Vec<_>>();
+        modulus_backend.reduce_vec(&mut c);
+        assert_eq!(c, c_expected);
+
+        // u32 scalars
+        let modulus_backend =
+            <NativeModulusBackend<u32> as ModulusBackendConfig<u32>>::initialise(PRIME_KYBER);
+        for _ in 0..K {
+            let a = rng.gen::<u32>();
+            assert_eq!(modulus_backend.reduce(a), a % PRIME_KYBER);
+            let a = rng.gen::<u64>();
+            assert_eq!(
+                modulus_backend.reduce_doubled(a),
+                (a % PRIME_KYBER as u64) as u32
+            );
+            let a = rng.gen::<i64>();
+            assert_eq!(
+                modulus_backend.reduce_i64(a),
+                a.rem_euclid(PRIME_KYBER as i64) as u32
+            );
+        }
+    }
 }
@@ -0,0 +1,103 @@
+use crate::{core_crypto::num::UnsignedInteger, utils::FastModularInverse};
+use itertools::izip;
+use num_traits::{AsPrimitive, NumCast};
+
+pub trait PlantardBackendConfig<Scalar, ScalarDoubled>
+where
+    Scalar: UnsignedInteger + AsPrimitive<ScalarDoubled> + 'static,
+    ScalarDoubled: UnsignedInteger + AsPrimitive<Scalar> + FastModularInverse + 'static,
+{
+    /// Returns q^{-1} (mod 2^{2W}) and -2^{2W} (mod q), where `W` is bits in Scalar.
+    ///
+    /// Constants are computed for any odd modulus. Bound q < 2^W/\phi required by Plantard
+    /// reduction is checked by `PlantardBackend::plantard_representation`.
+    fn initialise(modulus: Scalar) -> (ScalarDoubled, Scalar) {
+        let q_inv = ScalarDoubled::fast_inverse(modulus.as_());
+
+        // 2^W (mod q)
+        let r_modq = (ScalarDoubled::one() << (Scalar::BITS as usize)) % modulus.as_();
+        let r_square_modq = (r_modq * r_modq) % modulus.as_();
+        let minus_r_square_modq = (modulus.as_() - r_square_modq) % modulus.as_();
+
+        (q_inv, minus_r_square_modq.as_())
+    }
+}
+
+/// Plantard's modular multiplication.
+///
+/// For `a, b \in [0, q)` Plantard reduction outputs `-ab * 2^{-2W} (mod q)` in [0, q) with
+/// a single conditional-free reduction step, given `b' = b * q^{-1} (mod 2^{2W})`. Folding
+/// `-2^{2W}` into the precomputed constant (see `plantard_representation`) gives `ab (mod q)`.
+/// This makes it well suited for multiplication by constants (for ex, twiddle factors).
+///
+/// Modulus must be smaller than 2^W/\phi, where \phi is the golden ratio.
+///
+/// - [Reference](https://thomas-plantard.github.io/pdf/Plantard21.pdf)
+pub trait PlantardBackend<Scalar, ScalarDoubled>
+where
+    Scalar: UnsignedInteger + AsPrimitive<ScalarDoubled> + 'static,
+    ScalarDoubled: UnsignedInteger + AsPrimitive<Scalar> + 'static,
+{
+    fn modulus(&self) -> Scalar;
+
+    /// q^{-1} (mod 2^{2W})
+    fn q_inverse_mod_r_square(&self) -> ScalarDoubled;
+
+    /// -2^{2W} (mod q)
+    fn minus_r_square_modq(&self) -> Scalar;
+
+    /// Returns plantard representation `b * (-2^{2W}) * q^{-1} (mod 2^{2W})` of `b < q`.
+    ///
+    /// Panics if modulus is not smaller than 2^W/\phi, since `plantard_mul` outputs would be
+    /// incorrect.
+    fn plantard_representation(&self, b: Scalar) -> ScalarDoubled {
+        let q = self.modulus();
+        // We check 1619q < 1000 * 2^W, which is slightly stricter than q < 2^W/\phi
+        assert!(
+            q.leading_zeros() >= 1
+                && (q.as_() * <ScalarDoubled as NumCast>::from(1619).unwrap())
+                    < (<ScalarDoubled as NumCast>::from(1000).unwrap() << (Scalar::BITS as usize)),
+            "Modulus {q} >= 2^{}/phi",
+            Scalar::BITS
+        );
+        debug_assert!(b < q, "Input {b} >= (q){q}");
+
+        let b_r = (b.as_() * self.minus_r_square_modq().as_()) % q.as_();
+        b_r.wrapping_mul(&self.q_inverse_mod_r_square())
+    }
+
+    /// Outputs a * b (mod q) in [0, q), where `b_plantard` is plantard representation of `b`
+    #[inline]
+    fn plantard_mul(&self, a: Scalar, b_plantard: ScalarDoubled) -> Scalar {
+        let q = self.modulus();
+        debug_assert!(a < q, "Input {a} >= (q){q}");
+
+        let w = Scalar::BITS as usize;
+        let t: Scalar = (a.as_().wrapping_mul(&b_plantard) >> w).as_();
+        let c: Scalar = (((t.as_() + ScalarDoubled::one()) * q.as_()) >> w).as_();
+        debug_assert!(c < q, "Output {c} >= (q){q}");
+        c
+    }
+
+    /// Returns plantard representations of `b`
+    fn plantard_representation_vec(&self, b: &[Scalar]) -> Vec<ScalarDoubled> {
+        b.iter()
+            .map(|b0| self.plantard_representation(*b0))
+            .collect()
+    }
+
+    /// Outputs a_i = a_i * b (mod q) in [0, q)
+    fn plantard_scalar_mul_vec(&self, a: &mut [Scalar], b_plantard: ScalarDoubled) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.plantard_mul(*a0, b_plantard);
+        })
+    }
+
+    /// Outputs a_i = a_i * b_i (mod q) in [0, q), where `b_plantard` are plantard representations
+    /// of fixed `b`.
+    fn plantard_mul_vec(&self, a: &mut [Scalar], b_plantard: &[ScalarDoubled]) {
+        izip!(a.iter_mut(), b_plantard.iter()).for_each(|(a0, b0)| {
+            *a0 = self.plantard_mul(*a0, *b0);
+        })
+    }
+}
@@ -1,9 +1,81 @@
+use crate::core_crypto::num::{UnsignedInteger, UnsignedIntegerDoubled};
+use itertools::izip;
+use num_traits::AsPrimitive;
+
 pub trait ShoupRepresentationFq {
-    fn shoup_representation_fq(&self, q: Self) -> u64;
+    /// Returns shoup representation `floor(self * 2^{BITS} / q)` of `self`, where `self < q`.
+    fn shoup_representation_fq(&self, q: Self) -> Self;
+}
+
+impl<Scalar: UnsignedIntegerDoubled> ShoupRepresentationFq for Scalar {
+    fn shoup_representation_fq(&self, q: Self) -> Self {
+        debug_assert!(*self < q, "Input {self} >= (q){q}");
+        let a: Scalar::Doubled = self.as_();
+        ((a << (Scalar::BITS as usize)) / q.as_()).as_()
+    }
 }
 
-impl ShoupRepresentationFq for u64 {
-    fn shoup_representation_fq(&self, q: Self) -> u64 {
-        ((*self as u128 * (1u128 << 64)) / q as u128) as u64
+/// Shoup's modular multiplication by a constant `b` with precomputed
+/// `b_shoup = floor(b * 2^{BITS} / q)` (see [ShoupRepresentationFq]).
+///
+/// Lazy variants output values in [0, 2q) and accept any `a < 2^{BITS}`. Thus modulus must be
+/// smaller than 2^{BITS - 1}.
+///
+/// - [Reference](https://arxiv.org/pdf/1205.2926.pdf) (Algorithm 2)
+pub trait ShoupBackend<Scalar, ScalarDoubled>
+where
+    Scalar: UnsignedInteger + AsPrimitive<ScalarDoubled> + 'static,
+    ScalarDoubled: UnsignedInteger + AsPrimitive<Scalar> + 'static,
+{
+    fn modulus(&self) -> Scalar;
+
+    /// Outputs a * b (mod q) in [0, 2q)
+    #[inline]
+    fn shoup_mul_lazy(&self, a: Scalar, b: Scalar, b_shoup: Scalar) -> Scalar {
+        debug_assert!(b < self.modulus(), "Input {b} >= (q){}", self.modulus());
+
+        let k: Scalar = ((a.as_() * b_shoup.as_()) >> (Scalar::BITS as usize)).as_();
+        a.wrapping_mul(&b)
+            .wrapping_sub(&k.wrapping_mul(&self.modulus()))
+    }
+
+    /// Outputs a * b (mod q) in [0, q)
+    #[inline]
+    fn shoup_mul(&self, a: Scalar, b: Scalar, b_shoup: Scalar) -> Scalar {
+        let mut c = self.shoup_mul_lazy(a, b, b_shoup);
+        if c >= self.modulus() {
+            c -= self.modulus();
+        }
+        c
+    }
+
+    /// Outputs a_i = a_i * b (mod q) in [0, 2q)
+    fn shoup_scalar_mul_lazy_vec(&self, a: &mut [Scalar], b: Scalar, b_shoup: Scalar) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.shoup_mul_lazy(*a0, b, b_shoup);
+        })
+    }
+
+    /// Outputs a_i = a_i * b (mod q) in [0, q)
+    fn shoup_scalar_mul_vec(&self, a: &mut [Scalar], b: Scalar, b_shoup: Scalar) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.shoup_mul(*a0, b, b_shoup);
+        })
+    }
+
+    /// Outputs a_i = a_i * b_i (mod q) in [0, 2q), where `b_shoup` are shoup representations of
+    /// fixed `b`.
+    fn shoup_mul_lazy_vec(&self, a: &mut [Scalar], b: &[Scalar], b_shoup: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter(), b_shoup.iter()).for_each(|(a0, b0, b0_shoup)| {
+            *a0 = self.shoup_mul_lazy(*a0, *b0, *b0_shoup);
+        })
+    }
+
+    /// Outputs a_i = a_i * b_i (mod q) in [0, q), where `b_shoup` are shoup representations of
+    /// fixed `b`.
+    fn shoup_mul_vec(&self, a: &mut [Scalar], b: &[Scalar], b_shoup: &[Scalar]) {
+        izip!(a.iter_mut(), b.iter(), b_shoup.iter()).for_each(|(a0, b0, b0_shoup)| {
+            *a0 = self.shoup_mul(*a0, *b0, *b0_shoup);
+        })
     }
 }
@@ -0,0 +1,561 @@
+use super::{BarrettBackend, ModulusBackendConfig, ModulusVecBackend, NativeModulusBackend};
+
+/// SIMD instruction set used by [SimdModulusBackend]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimdLevel {
+    /// Fallback to scalar routines of `NativeModulusBackend`
+    Portable,
+    Avx2,
+    Avx512,
+}
+
+impl SimdLevel {
+    /// Returns widest SIMD instruction set supported by the running CPU
+    pub fn detect() -> SimdLevel {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return SimdLevel::Avx512;
+            }
+            if is_x86_feature_detected!("avx2") {
+                return SimdLevel::Avx2;
+            }
+        }
+        SimdLevel::Portable
+    }
+
+    /// Whether the running CPU supports the instruction set
+    pub fn is_supported(&self) -> bool {
+        match self {
+            SimdLevel::Portable => true,
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx512 => is_x86_feature_detected!("avx512f"),
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false,
+        }
+    }
+}
+
+/// Barrett constants required by SIMD kernels. Kernels implement the same barrett reduction
+/// as `BarrettBackend::mul_mod_fast` so that outputs are bit for bit equal.
+#[derive(Clone, Copy)]
+struct BarrettParams {
+    q: u64,
+    mu: u64,
+    /// n - 2
+    ab_shift: u32,
+    /// \alpha + 2
+    quotient_shift: u32,
+}
+
+/// `ModulusVecBackend` for u64 moduli that uses AVX2/AVX-512 when the running CPU supports them.
+///
+/// CPU features are detected at runtime in `initialise`. Routines without a SIMD kernel, and the tail of
+/// vectors that do not fill a SIMD register, fallback to `NativeModulusBackend`.
+///
+/// SIMD kernels require the intermediate `ab / 2^{n-2}` of barrett reduction to fit in 64 bits, which
+/// holds for moduli supported by `NativeModulusBackend<u64>` (i.e. at most 60 bits) as long as inputs to
+/// multiplication are < 2q.
+pub struct SimdModulusBackend {
+    native: NativeModulusBackend<u64>,
+    params: BarrettParams,
+    level: SimdLevel,
+}
+
+impl SimdModulusBackend {
+    /// Returns backend that uses `level` irrespective of what is detected at runtime.
+    ///
+    /// Panics if `level` is not supported by the running CPU.
+    pub fn with_level(modulus: u64, level: SimdLevel) -> SimdModulusBackend {
+        assert!(level.is_supported(), "{level:?} is not supported by CPU");
+
+        let native = NativeModulusBackend::initialise(modulus);
+        let params = BarrettParams {
+            q: modulus,
+            mu: native.barrett_constant(),
+            ab_shift: (native.modulus_bits() - 2) as u32,
+            quotient_shift: (native.barrett_alpha() + 2) as u32,
+        };
+
+        SimdModulusBackend {
+            native,
+            params,
+            level,
+        }
+    }
+
+    pub fn level(&self) -> SimdLevel {
+        self.level
+    }
+
+    pub fn native(&self) -> &NativeModulusBackend<u64> {
+        &self.native
+    }
+}
+
+impl ModulusBackendConfig<u64> for SimdModulusBackend {
+    fn initialise(modulus: u64) -> SimdModulusBackend {
+        SimdModulusBackend::with_level(modulus, SimdLevel::detect())
+    }
+}
+
+/// Dispatches to kernel of selected SIMD level. Kernels return no. of elements processed and
+/// the rest are processed by the native backend.
+macro_rules! dispatch {
+    ($self:ident, $kernel:ident($($arg:expr),*)) => {
+        match $self.level {
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx512 => unsafe { avx512::$kernel($($arg),*) },
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx2 => unsafe { avx2::$kernel($($arg),*) },
+            _ => 0,
+        }
+    };
+}
+
+impl ModulusVecBackend<u64> for SimdModulusBackend {
+    fn add_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        let done = dispatch!(self, add_mod_vec(a, b, &self.params));
+        self.native.add_mod_vec(&mut a[done..], &b[done..]);
+    }
+
+    fn sub_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        let done = dispatch!(self, sub_mod_vec(a, b, &self.params));
+        self.native.sub_mod_vec(&mut a[done..], &b[done..]);
+    }
+
+    fn mul_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        let done = dispatch!(self, mul_mod_vec(a, b, &self.params));
+        self.native.mul_mod_vec(&mut a[done..], &b[done..]);
+    }
+
+    fn neg_mod_vec(&self, a: &mut [u64]) {
+        let done = dispatch!(self, neg_mod_vec(a, &self.params));
+        self.native.neg_mod_vec(&mut a[done..]);
+    }
+
+    fn scalar_mul_mod_vec(&self, a: &mut [u64], b: u64) {
+        let done = dispatch!(self, scalar_mul_mod_vec(a, b, &self.params));
+        self.native.scalar_mul_mod_vec(&mut a[done..], b);
+    }
+
+    fn fma_mod_vec(&self, a: &mut [u64], b: &[u64], c: &[u64]) {
+        let done = dispatch!(self, fma_mod_vec(a, b, c, &self.params));
+        self.native
+            .fma_mod_vec(&mut a[done..], &b[done..], &c[done..]);
+    }
+
+    fn fms_mod_vec(&self, a: &mut [u64], b: &[u64], c: &[u64]) {
+        let done = dispatch!(self, fms_mod_vec(a, b, c, &self.params));
+        self.native
+            .fms_mod_vec(&mut a[done..], &b[done..], &c[done..]);
+    }
+
+    fn inner_product_mod(&self, a: &[u64], b: &[u64]) -> u64 {
+        self.native.inner_product_mod(a, b)
+    }
+
+    fn reduce_vec(&self, a: &mut [u64]) {
+        self.native.reduce_vec(a)
+    }
+
+    fn reduce_doubled_vec(&self, a: &mut [u64], b: &[u128]) {
+        self.native.reduce_doubled_vec(a, b)
+    }
+
+    fn reduce_i64_vec(&self, a: &mut [u64], b: &[i64]) {
+        self.native.reduce_i64_vec(a, b)
+    }
+
+    fn to_centered_i64_vec(&self, a: &mut [i64], b: &[u64]) {
+        self.native.to_centered_i64_vec(a, b)
+    }
+}
+
+/// Generates SIMD kernels given lane count and primitive vector operations of an instruction set.
+///
+/// Since there is no 64x64 bit multiplication, 128 bit products are assembled from 32x32 bit products.
+#[cfg(target_arch = "x86_64")]
+macro_rules! simd_kernels {
+    ($feature:literal, $lanes:literal, $vec:ty, $load:ident, $store:ident, $set1:ident, $add:ident,
+     $sub:ident, $and:ident, $or:ident, $mul_epu32:ident, $srli:ident, $slli:ident, $srl:ident,
+     $sll:ident, $lt:ident, $ge:ident, $eq_zero:ident) => {
+        use super::BarrettParams;
+        use std::arch::x86_64::*;
+
+        const LANES: usize = $lanes;
+
+        /// Returns (hi, lo) 64 bit halves of 128 bit product a*b
+        #[target_feature(enable = $feature)]
+        #[inline]
+        unsafe fn mul_wide(a: $vec, b: $vec) -> ($vec, $vec) {
+            let mask32 = $set1(0xffff_ffff);
+            let a_hi = $srli::<32>(a);
+            let b_hi = $srli::<32>(b);
+
+            let p00 = $mul_epu32(a, b);
+            let p01 = $mul_epu32(a, b_hi);
+            let p10 = $mul_epu32(a_hi, b);
+            let p11 = $mul_epu32(a_hi, b_hi);
+
+            // middle 64 bits along with carry from lower 32 bits. Cannot overflow.
+            let mid = $add($add($srli::<32>(p00), $and(p01, mask32)), $and(p10, mask32));
+
+            let lo = $or($slli::<32>(mid), $and(p00, mask32));
+            let hi = $add(
+                $add(p11, $srli::<32>(p01)),
+                $add($srli::<32>(p10), $srli::<32>(mid)),
+            );
+            (hi, lo)
+        }
+
+        /// Returns lower 64 bits of a*b
+        #[target_feature(enable = $feature)]
+        #[inline]
+        unsafe fn mul_lo(a: $vec, b: $vec) -> $vec {
+            let a_hi = $srli::<32>(a);
+            let b_hi = $srli::<32>(b);
+            let cross = $add($mul_epu32(a, b_hi), $mul_epu32(a_hi, b));
+            $add($mul_epu32(a, b), $slli::<32>(cross))
+        }
+
+        /// Returns lower 64 bits of (hi, lo) >> s.
+        ///
+        /// Shift counts >= 64 output 0, so `(lo >> s) | (hi << (64 - s)) | (hi >> (s - 64))` works
+        /// for all `s` in [0, 128) when the out of range terms are shifted by 64 instead.
+        #[target_feature(enable = $feature)]
+        #[inline]
+        unsafe fn shr_wide(hi: $vec, lo: $vec, s: u32) -> $vec {
+            let c0 = _mm_set_epi64x(0, s as i64);
+            let c1 = _mm_set_epi64x(0, if s <= 64 { 64 - s as i64 } else { 64 });
+            let c2 = _mm_set_epi64x(0, if s >= 64 { s as i64 - 64 } else { 64 });
+            $or($or($srl(lo, c0), $sll(hi, c1)), $srl(hi, c2))
+        }
+
+        #[target_feature(enable = $feature)]
+        #[inline]
+        unsafe fn add_mod(a: $vec, b: $vec, q: $vec) -> $vec {
+            let c = $add(a, b);
+            $ge(c, q, $sub(c, q))
+        }
+
+        #[target_feature(enable = $feature)]
+        #[inline]
+        unsafe fn sub_mod(a: $vec, b: $vec, q: $vec) -> $vec {
+            let c = $sub(a, b);
+            $lt(a, b, $add(c, q), c)
+        }
+
+        /// Same as `BarrettBackend::mul_mod_fast`
+        #[target_feature(enable = $feature)]
+        #[inline]
+        unsafe fn mul_mod(a: $vec, b: $vec, q: $vec, mu: $vec, params: &BarrettParams) -> $vec {
+            let (ab_hi, ab_lo) = mul_wide(a, b);
+
+            // ab / 2^{n-2}
+            let tmp = shr_wide(ab_hi, ab_lo, params.ab_shift);
+
+            // (tmp * \mu) / 2^{\alpha + 2}
+            let (t_hi, t_lo) = mul_wide(tmp, mu);
+            let quotient = shr_wide(t_hi, t_lo, params.quotient_shift);
+
+            let r = $sub(ab_lo, mul_lo(quotient, q));
+            $ge(r, q, $sub(r, q))
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn add_mod_vec(
+            a: &mut [u64],
+            b: &[u64],
+            params: &BarrettParams,
+        ) -> usize {
+            let n = a.len().min(b.len()) / LANES * LANES;
+            let q = $set1(params.q as i64);
+            for i in (0..n).step_by(LANES) {
+                let a_ptr = a.as_mut_ptr().add(i) as *mut $vec;
+                let va = $load(a_ptr as *const $vec);
+                let vb = $load(b.as_ptr().add(i) as *const $vec);
+                $store(a_ptr, add_mod(va, vb, q));
+            }
+            n
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn sub_mod_vec(
+            a: &mut [u64],
+            b: &[u64],
+            params: &BarrettParams,
+        ) -> usize {
+            let n = a.len().min(b.len()) / LANES * LANES;
+            let q = $set1(params.q as i64);
+            for i in (0..n).step_by(LANES) {
+                let a_ptr = a.as_mut_ptr().add(i) as *mut $vec;
+                let va = $load(a_ptr as *const $vec);
+                let vb = $load(b.as_ptr().add(i) as *const $vec);
+                $store(a_ptr, sub_mod(va, vb, q));
+            }
+            n
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn mul_mod_vec(
+            a: &mut [u64],
+            b: &[u64],
+            params: &BarrettParams,
+        ) -> usize {
+            let n = a.len().min(b.len()) / LANES * LANES;
+            let q = $set1(params.q as i64);
+            let mu = $set1(params.mu as i64);
+            for i in (0..n).step_by(LANES) {
+                let a_ptr = a.as_mut_ptr().add(i) as *mut $vec;
+                let va = $load(a_ptr as *const $vec);
+                let vb = $load(b.as_ptr().add(i) as *const $vec);
+                $store(a_ptr, mul_mod(va, vb, q, mu, params));
+            }
+            n
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn neg_mod_vec(a: &mut [u64], params: &BarrettParams) -> usize {
+            let n = a.len() / LANES * LANES;
+            let q = $set1(params.q as i64);
+            for i in (0..n).step_by(LANES) {
+                let a_ptr = a.as_mut_ptr().add(i) as *mut $vec;
+                let va = $load(a_ptr as *const $vec);
+                $store(a_ptr, $eq_zero(va, $sub(q, va)));
+            }
+            n
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn scalar_mul_mod_vec(
+            a: &mut [u64],
+            b: u64,
+            params: &BarrettParams,
+        ) -> usize {
+            let n = a.len() / LANES * LANES;
+            let q = $set1(params.q as i64);
+            let mu = $set1(params.mu as i64);
+            let vb = $set1(b as i64);
+            for i in (0..n).step_by(LANES) {
+                let a_ptr = a.as_mut_ptr().add(i) as *mut $vec;
+                let va = $load(a_ptr as *const $vec);
+                $store(a_ptr, mul_mod(va, vb, q, mu, params));
+            }
+            n
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn fma_mod_vec(
+            a: &mut [u64],
+            b: &[u64],
+            c: &[u64],
+            params: &BarrettParams,
+        ) -> usize {
+            let n = a.len().min(b.len()).min(c.len()) / LANES * LANES;
+            let q = $set1(params.q as i64);
+            let mu = $set1(params.mu as i64);
+            for i in (0..n).step_by(LANES) {
+                let a_ptr = a.as_mut_ptr().add(i) as *mut $vec;
+                let va = $load(a_ptr as *const $vec);
+                let vb = $load(b.as_ptr().add(i) as *const $vec);
+                let vc = $load(c.as_ptr().add(i) as *const $vec);
+                $store(a_ptr, add_mod(va, mul_mod(vb, vc, q, mu, params), q));
+            }
+            n
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn fms_mod_vec(
+            a: &mut [u64],
+            b: &[u64],
+            c: &[u64],
+            params: &BarrettParams,
+        ) -> usize {
+            let n = a.len().min(b.len()).min(c.len()) / LANES * LANES;
+            let q = $set1(params.q as i64);
+            let mu = $set1(params.mu as i64);
+            for i in (0..n).step_by(LANES) {
+                let a_ptr = a.as_mut_ptr().add(i) as *mut $vec;
+                let va = $load(a_ptr as *const $vec);
+                let vb = $load(b.as_ptr().add(i) as *const $vec);
+                let vc = $load(c.as_ptr().add(i) as *const $vec);
+                $store(a_ptr, sub_mod(va, mul_mod(vb, vc, q, mu, params), q));
+            }
+            n
+        }
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    // AVX2 only has signed 64 bit comparison. It is fine since all values are < 2^62.
+
+    /// Returns `if_true` where a >= b and a otherwise
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn select_ge(a: __m256i, b: __m256i, if_true: __m256i) -> __m256i {
+        _mm256_blendv_epi8(if_true, a, _mm256_cmpgt_epi64(b, a))
+    }
+
+    /// Returns `if_true` where a < b and `if_false` otherwise
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn select_lt(a: __m256i, b: __m256i, if_true: __m256i, if_false: __m256i) -> __m256i {
+        _mm256_blendv_epi8(if_false, if_true, _mm256_cmpgt_epi64(b, a))
+    }
+
+    /// Returns 0 where a == 0 and `otherwise` otherwise
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn zero_if_zero(a: __m256i, otherwise: __m256i) -> __m256i {
+        _mm256_andnot_si256(_mm256_cmpeq_epi64(a, _mm256_setzero_si256()), otherwise)
+    }
+
+    simd_kernels!(
+        "avx2",
+        4,
+        __m256i,
+        _mm256_loadu_si256,
+        _mm256_storeu_si256,
+        _mm256_set1_epi64x,
+        _mm256_add_epi64,
+        _mm256_sub_epi64,
+        _mm256_and_si256,
+        _mm256_or_si256,
+        _mm256_mul_epu32,
+        _mm256_srli_epi64,
+        _mm256_slli_epi64,
+        _mm256_srl_epi64,
+        _mm256_sll_epi64,
+        select_lt,
+        select_ge,
+        zero_if_zero
+    );
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx512 {
+    /// Returns `if_true` where a >= b and a otherwise
+    #[target_feature(enable = "avx512f")]
+    #[inline]
+    unsafe fn select_ge(a: __m512i, b: __m512i, if_true: __m512i) -> __m512i {
+        _mm512_mask_blend_epi64(_mm512_cmpge_epu64_mask(a, b), a, if_true)
+    }
+
+    /// Returns `if_true` where a < b and `if_false` otherwise
+    #[target_feature(enable = "avx512f")]
+    #[inline]
+    unsafe fn select_lt(a: __m512i, b: __m512i, if_true: __m512i, if_false: __m512i) -> __m512i {
+        _mm512_mask_blend_epi64(_mm512_cmplt_epu64_mask(a, b), if_false, if_true)
+    }
+
+    /// Returns 0 where a == 0 and `otherwise` otherwise
+    #[target_feature(enable = "avx512f")]
+    #[inline]
+    unsafe fn zero_if_zero(a: __m512i, otherwise: __m512i) -> __m512i {
+        _mm512_maskz_mov_epi64(_mm512_test_epi64_mask(a, a), otherwise)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    #[inline]
+    unsafe fn load(ptr: *const __m512i) -> __m512i {
+        _mm512_loadu_epi64(ptr as *const i64)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    #[inline]
+    unsafe fn store(ptr: *mut __m512i, v: __m512i) {
+        _mm512_storeu_epi64(ptr as *mut i64, v)
+    }
+
+    simd_kernels!(
+        "avx512f",
+        8,
+        __m512i,
+        load,
+        store,
+        _mm512_set1_epi64,
+        _mm512_add_epi64,
+        _mm512_sub_epi64,
+        _mm512_and_si512,
+        _mm512_or_si512,
+        _mm512_mul_epu32,
+        _mm512_srli_epi64,
+        _mm512_slli_epi64,
+        _mm512_srl_epi64,
+        _mm512_sll_epi64,
+        select_lt,
+        select_ge,
+        zero_if_zero
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    const PRIME_60_BITS: u64 = 1152921504606748673;
+    const PRIME_50_BITS: u64 = 1125899906826241;
+
+    fn random_vec(size: usize, bound: u64) -> Vec<u64> {
+        let mut rng = thread_rng();
+        (0..size).map(|_| rng.gen_range(0..bound)).collect()
+    }
+
+    #[test]
+    fn simd_modulus_backend_matches_native() {
+        let levels = [SimdLevel::Portable, SimdLevel::Avx2, SimdLevel::Avx512]
+            .into_iter()
+            .filter(|l| l.is_supported())
+            .collect::<Vec<_>>();
+
+        for p in [PRIME_60_BITS, PRIME_50_BITS] {
+            let native = NativeModulusBackend::initialise(p);
+            for level in levels.iter() {
+                let simd = SimdModulusBackend::with_level(p, *level);
+
+                macro_rules! check {
+                    ($a:expr, $op:ident($($arg:expr),*)) => {
+                        let mut expected = $a.to_vec();
+                        native.$op(&mut expected, $($arg),*);
+                        let mut out = $a.to_vec();
+                        simd.$op(&mut out, $($arg),*);
+                        assert_eq!(out, expected, "{} mismatch for {level:?}", stringify!($op));
+                    };
+                }
+
+                // sizes that do not fill SIMD registers check the scalar tail
+                for size in [0, 3, 8, 127, 1024] {
+                    let a = random_vec(size, p);
+                    let b = random_vec(size, p);
+                    let c = random_vec(size, p);
+
+                    check!(a, add_mod_vec(&b));
+                    check!(a, sub_mod_vec(&b));
+                    check!(a, mul_mod_vec(&b));
+                    check!(a, neg_mod_vec());
+                    check!(a, scalar_mul_mod_vec(b.first().copied().unwrap_or(p - 1)));
+                    check!(a, fma_mod_vec(&b, &c));
+                    check!(a, fms_mod_vec(&b, &c));
+
+                    // multiplication inputs in [0, 2q)
+                    let a = random_vec(size, 2 * p);
+                    let b = random_vec(size, 2 * p);
+                    check!(a, mul_mod_vec(&b));
+                }
+
+                // edge values
+                let a = [0, 1, p - 1, p - 2, 0, p - 1, 1, 2];
+                let b = [p - 1, p - 1, p - 1, 0, 0, 1, 1, p - 2];
+                check!(a, add_mod_vec(&b));
+                check!(a, sub_mod_vec(&b));
+                check!(a, mul_mod_vec(&b));
+                check!(a, neg_mod_vec());
+            }
+        }
+    }
+}
@@ -0,0 +1,453 @@
+use super::{ModulusBackendConfig, ModulusVecBackend};
+use itertools::izip;
+
+/// Goldilocks prime 2^64 - 2^32 + 1
+pub const GOLDILOCKS_PRIME: u64 = 0xffff_ffff_0000_0001;
+
+/// Special forms of primes that admit reduction with shifts and adds only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecialForm {
+    /// q = 2^64 - 2^32 + 1
+    Goldilocks,
+    /// q = 2^k - 2^j + 1, where multiplication by c = 2^j - 1 is a shift and a subtraction
+    Solinas { k: u32, j: u32 },
+    /// q = 2^k - c, for small c
+    PseudoMersenne { k: u32, c: u64 },
+}
+
+impl SpecialForm {
+    /// Detects special form of modulus `q`, if any.
+    ///
+    /// Apart from Goldilocks prime, `q` must be at most 63 bits and `c = 2^k - q` must satisfy
+    /// `c^2 + 2c < 2^k`. The latter guarantees that two folds reduce product of two values in
+    /// [0, q) to [0, 2q).
+    pub fn detect(q: u64) -> Option<SpecialForm> {
+        if q == GOLDILOCKS_PRIME {
+            return Some(SpecialForm::Goldilocks);
+        }
+
+        let k = u64::BITS - q.leading_zeros();
+        if !(2..=63).contains(&k) {
+            return None;
+        }
+
+        let c = (1u64 << k) - q;
+        if (c as u128) * (c as u128 + 2) >= (1u128 << k) {
+            return None;
+        }
+
+        if (c + 1).is_power_of_two() {
+            Some(SpecialForm::Solinas {
+                k,
+                j: (c + 1).trailing_zeros(),
+            })
+        } else {
+            Some(SpecialForm::PseudoMersenne { k, c })
+        }
+    }
+}
+
+/// Modulus backend for u64 moduli of [SpecialForm].
+///
+/// Reduction folds high bits onto low bits using 2^k = c (mod q) and requires no barrett or
+/// montgomery constants. Unlike `NativeModulusBackend` this supports moduli upto 64 bits
+/// (Goldilocks) and moduli of 61-63 bits.
+pub struct SpecialFormModulusBackend {
+    modulus: u64,
+    form: SpecialForm,
+}
+
+impl SpecialFormModulusBackend {
+    /// Returns backend for `modulus` if it is of [SpecialForm]
+    pub fn try_initialise(modulus: u64) -> Option<SpecialFormModulusBackend> {
+        SpecialForm::detect(modulus).map(|form| SpecialFormModulusBackend { modulus, form })
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    pub fn form(&self) -> SpecialForm {
+        self.form
+    }
+
+    /// Reduces `a` to [0, q)
+    #[inline]
+    pub fn reduce_u128(&self, a: u128) -> u64 {
+        match self.form {
+            SpecialForm::Goldilocks => reduce_goldilocks(a),
+            SpecialForm::Solinas { k, j } => {
+                // hi * (2^j - 1)
+                self.reduce_folded(fold(a, k, |hi| (hi << j) - hi), k)
+            }
+            SpecialForm::PseudoMersenne { k, c } => {
+                self.reduce_folded(fold(a, k, |hi| hi * c as u128), k)
+            }
+        }
+    }
+
+    /// Reduces `a < 2^k` to [0, q)
+    #[inline]
+    fn reduce_folded(&self, a: u128, k: u32) -> u64 {
+        debug_assert!(a >> k == 0);
+        let mut a = a as u64;
+        // 2^k - c >= 2^{k-1}, thus a < 2q
+        if a >= self.modulus {
+            a -= self.modulus;
+        }
+        a
+    }
+
+    #[inline]
+    pub fn reduce(&self, a: u64) -> u64 {
+        match self.form {
+            SpecialForm::Goldilocks => {
+                if a >= self.modulus {
+                    a - self.modulus
+                } else {
+                    a
+                }
+            }
+            _ => self.reduce_u128(a as u128),
+        }
+    }
+
+    #[inline]
+    pub fn add_mod(&self, a: u64, b: u64) -> u64 {
+        debug_assert!(a < self.modulus, "Input {a} >= (modulus){}", self.modulus);
+        debug_assert!(b < self.modulus, "Input {b} >= (modulus){}", self.modulus);
+
+        // For goldilocks a + b may overflow 2^64, in which case wrapping subtraction of q equals
+        // a + b - q
+        let (c, overflow) = a.overflowing_add(b);
+        if overflow || c >= self.modulus {
+            c.wrapping_sub(self.modulus)
+        } else {
+            c
+        }
+    }
+
+    #[inline]
+    pub fn sub_mod(&self, a: u64, b: u64) -> u64 {
+        debug_assert!(a < self.modulus, "Input {a} >= (modulus){}", self.modulus);
+        debug_assert!(b < self.modulus, "Input {b} >= (modulus){}", self.modulus);
+
+        let (c, underflow) = a.overflowing_sub(b);
+        if underflow {
+            c.wrapping_add(self.modulus)
+        } else {
+            c
+        }
+    }
+
+    #[inline]
+    pub fn neg_mod(&self, a: u64) -> u64 {
+        self.sub_mod(0, a)
+    }
+
+    #[inline]
+    pub fn mul_mod(&self, a: u64, b: u64) -> u64 {
+        self.reduce_u128(a as u128 * b as u128)
+    }
+}
+
+/// Folds `a` as `hi * 2^k + lo = hi * c + lo (mod q)` until `a < 2^k`.
+///
+/// Product of two values in [0, q) requires at most two folds.
+#[inline]
+fn fold<F: Fn(u128) -> u128>(mut a: u128, k: u32, mul_c: F) -> u128 {
+    let mask = (1u128 << k) - 1;
+    while a >> k != 0 {
+        a = mul_c(a >> k) + (a & mask);
+    }
+    a
+}
+
+/// Reduces `a` modulo goldilocks prime using 2^64 = 2^32 - 1 (mod q) and 2^96 = -1 (mod q)
+///
+/// - [Reference](https://github.com/0xPolygonZero/plonky2/blob/main/field/src/goldilocks_field.rs)
+#[inline]
+fn reduce_goldilocks(a: u128) -> u64 {
+    // 2^32 - 1
+    const EPSILON: u64 = 0xffff_ffff;
+
+    let a_lo = a as u64;
+    let a_hi = (a >> 64) as u64;
+    let a_hi_hi = a_hi >> 32;
+    let a_hi_lo = a_hi & EPSILON;
+
+    // a_lo - a_hi_hi
+    let (mut t0, borrow) = a_lo.overflowing_sub(a_hi_hi);
+    if borrow {
+        // adds q, i.e. subtracts 2^32 - 1 modulo 2^64
+        t0 = t0.wrapping_sub(EPSILON);
+    }
+
+    // a_hi_lo * (2^32 - 1)
+    let t1 = (a_hi_lo << 32) - a_hi_lo;
+    let (mut t2, carry) = t0.overflowing_add(t1);
+    if carry {
+        t2 = t2.wrapping_add(EPSILON);
+    }
+
+    if t2 >= GOLDILOCKS_PRIME {
+        t2 -= GOLDILOCKS_PRIME;
+    }
+    t2
+}
+
+impl ModulusBackendConfig<u64> for SpecialFormModulusBackend {
+    /// Panics if `modulus` is not of [SpecialForm]. Use `try_initialise` otherwise.
+    fn initialise(modulus: u64) -> Self {
+        SpecialFormModulusBackend::try_initialise(modulus)
+            .unwrap_or_else(|| panic!("Modulus {modulus} is not of special form"))
+    }
+}
+
+impl ModulusVecBackend<u64> for SpecialFormModulusBackend {
+    fn add_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.add_mod(*a0, *b0);
+        })
+    }
+
+    fn sub_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.sub_mod(*a0, *b0);
+        })
+    }
+
+    fn mul_mod_vec(&self, a: &mut [u64], b: &[u64]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.mul_mod(*a0, *b0);
+        })
+    }
+
+    fn neg_mod_vec(&self, a: &mut [u64]) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.neg_mod(*a0);
+        })
+    }
+
+    fn scalar_mul_mod_vec(&self, a: &mut [u64], b: u64) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.mul_mod(*a0, b);
+        })
+    }
+
+    fn fma_mod_vec(&self, a: &mut [u64], b: &[u64], c: &[u64]) {
+        izip!(a.iter_mut(), b.iter(), c.iter()).for_each(|(a0, b0, c0)| {
+            *a0 = self.add_mod(*a0, self.mul_mod(*b0, *c0));
+        })
+    }
+
+    fn fms_mod_vec(&self, a: &mut [u64], b: &[u64], c: &[u64]) {
+        izip!(a.iter_mut(), b.iter(), c.iter()).for_each(|(a0, b0, c0)| {
+            *a0 = self.sub_mod(*a0, self.mul_mod(*b0, *c0));
+        })
+    }
+
+    /// Sums reduced products in u128, which cannot overflow for fewer than 2^64 products, and
+    /// reduces once at the end.
+    fn inner_product_mod(&self, a: &[u64], b: &[u64]) -> u64 {
+        let sum = izip!(a.iter(), b.iter())
+            .fold(0u128, |acc, (a0, b0)| acc + self.mul_mod(*a0, *b0) as u128);
+        self.reduce_u128(sum)
+    }
+
+    fn reduce_vec(&self, a: &mut [u64]) {
+        a.iter_mut().for_each(|a0| {
+            *a0 = self.reduce(*a0);
+        })
+    }
+
+    fn reduce_doubled_vec(&self, a: &mut [u64], b: &[u128]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            *a0 = self.reduce_u128(*b0);
+        })
+    }
+
+    fn reduce_i64_vec(&self, a: &mut [u64], b: &[i64]) {
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            let r = self.reduce(b0.unsigned_abs());
+            *a0 = if *b0 < 0 { self.neg_mod(r) } else { r };
+        })
+    }
+
+    fn to_centered_i64_vec(&self, a: &mut [i64], b: &[u64]) {
+        let q = self.modulus;
+        izip!(a.iter_mut(), b.iter()).for_each(|(a0, b0)| {
+            debug_assert!(*b0 < q, "Input {b0} >= (modulus){q}");
+            // |b0 - q| < q/2 < 2^63 fits in i64 even for goldilocks
+            *a0 = if *b0 > q >> 1 {
+                (*b0 as i128 - q as i128) as i64
+            } else {
+                *b0 as i64
+            };
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_crypto::modulus::NativeModulusBackend;
+    use rand::{thread_rng, Rng};
+
+    const PRIME_60_BITS: u64 = 1152921504606748673;
+    const PRIME_MERSENNE_61: u64 = (1 << 61) - 1;
+    const PRIME_SOLINAS_59_BITS: u64 = (1 << 59) - (1 << 28) + 1;
+    const PRIME_PSEUDO_MERSENNE_62_BITS: u64 = (1 << 62) - 57;
+    const K: usize = 1000;
+
+    #[test]
+    fn special_form_detection_works() {
+        assert_eq!(
+            SpecialForm::detect(GOLDILOCKS_PRIME),
+            Some(SpecialForm::Goldilocks)
+        );
+        assert_eq!(
+            SpecialForm::detect(PRIME_MERSENNE_61),
+            Some(SpecialForm::Solinas { k: 61, j: 1 })
+        );
+        assert_eq!(
+            SpecialForm::detect(PRIME_SOLINAS_59_BITS),
+            Some(SpecialForm::Solinas { k: 59, j: 28 })
+        );
+        assert_eq!(
+            SpecialForm::detect(PRIME_PSEUDO_MERSENNE_62_BITS),
+            Some(SpecialForm::PseudoMersenne { k: 62, c: 57 })
+        );
+        assert_eq!(
+            SpecialForm::detect(PRIME_60_BITS),
+            Some(SpecialForm::PseudoMersenne { k: 60, c: 98303 })
+        );
+
+        // c too large
+        assert_eq!(SpecialForm::detect(1000000007), None);
+        assert_eq!(SpecialForm::detect((1 << 40) - (1 << 21) + 1), None);
+        // 64 bits, but not goldilocks
+        assert_eq!(SpecialForm::detect(u64::MAX - 58), None);
+    }
+
+    #[test]
+    fn special_form_modulus_backend_works() {
+        let mut rng = thread_rng();
+        for p in [
+            GOLDILOCKS_PRIME,
+            PRIME_MERSENNE_61,
+            PRIME_SOLINAS_59_BITS,
+            PRIME_PSEUDO_MERSENNE_62_BITS,
+            PRIME_60_BITS,
+        ] {
+            let modulus = SpecialFormModulusBackend::initialise(p);
+            let p_u128 = p as u128;
+
+            let edge = [0, 1, 2, p >> 1, (p >> 1) + 1, p - 2, p - 1];
+            let random = (0..K).map(|_| rng.gen::<u64>() % p);
+            let values = edge.into_iter().chain(random).collect::<Vec<_>>();
+            for (a, b) in izip!(values.iter(), values.iter().rev()) {
+                let (a, b) = (*a, *b);
+                assert_eq!(
+                    modulus.add_mod(a, b) as u128,
+                    (a as u128 + b as u128) % p_u128
+                );
+                assert_eq!(
+                    modulus.sub_mod(a, b) as u128,
+                    (a as u128 + p_u128 - b as u128) % p_u128
+                );
+                assert_eq!(modulus.neg_mod(a) as u128, (p_u128 - a as u128) % p_u128);
+                assert_eq!(
+                    modulus.mul_mod(a, b) as u128,
+                    (a as u128 * b as u128) % p_u128
+                );
+            }
+
+            for a in [0, u64::MAX, p, p + 1, u64::MAX - p]
+                .into_iter()
+                .chain((0..K).map(|_| rng.gen()))
+            {
+                assert_eq!(modulus.reduce(a), a % p);
+            }
+            for a in [0, u128::MAX, p_u128 * p_u128, p_u128 << 64]
+                .into_iter()
+                .chain((0..K).map(|_| rng.gen()))
+            {
+                assert_eq!(modulus.reduce_u128(a) as u128, a % p_u128);
+            }
+        }
+    }
+
+    #[test]
+    fn special_form_vec_backend_works() {
+        let mut rng = thread_rng();
+
+        // matches native backend where latter supports the modulus
+        let p = PRIME_60_BITS;
+        let modulus = SpecialFormModulusBackend::initialise(p);
+        let native = NativeModulusBackend::initialise(p);
+        let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let c = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        macro_rules! check {
+            ($a:expr, $op:ident($($arg:expr),*)) => {
+                let mut expected = $a.to_vec();
+                native.$op(&mut expected, $($arg),*);
+                let mut out = $a.to_vec();
+                modulus.$op(&mut out, $($arg),*);
+                assert_eq!(out, expected, "{} mismatch", stringify!($op));
+            };
+        }
+        check!(a, add_mod_vec(&b));
+        check!(a, sub_mod_vec(&b));
+        check!(a, mul_mod_vec(&b));
+        check!(a, neg_mod_vec());
+        check!(a, scalar_mul_mod_vec(b[0]));
+        check!(a, fma_mod_vec(&b, &c));
+        check!(a, fms_mod_vec(&b, &c));
+        assert_eq!(
+            modulus.inner_product_mod(&a, &b),
+            native.inner_product_mod(&a, &b)
+        );
+
+        let a_arbitrary = (0..K).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+        check!(a_arbitrary, reduce_vec());
+        let b_doubled = (0..K).map(|_| rng.gen::<u128>()).collect::<Vec<_>>();
+        check!(a, reduce_doubled_vec(&b_doubled));
+        let b_i64 = (0..K).map(|_| rng.gen::<i64>()).collect::<Vec<_>>();
+        check!(a, reduce_i64_vec(&b_i64));
+        let mut centered = vec![0i64; K];
+        let mut centered_expected = vec![0i64; K];
+        modulus.to_centered_i64_vec(&mut centered, &a);
+        native.to_centered_i64_vec(&mut centered_expected, &a);
+        assert_eq!(centered, centered_expected);
+
+        // goldilocks
+        let p = GOLDILOCKS_PRIME;
+        let modulus = SpecialFormModulusBackend::initialise(p);
+        let a = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let b = (0..K).map(|_| rng.gen::<u64>() % p).collect::<Vec<_>>();
+        let expected = izip!(a.iter(), b.iter()).fold(0u128, |acc, (a0, b0)| {
+            (acc + (*a0 as u128 * *b0 as u128)) % p as u128
+        });
+        assert_eq!(modulus.inner_product_mod(&a, &b) as u128, expected);
+
+        let b_i64 = [i64::MIN, -1, 0, 1, i64::MAX];
+        let mut out = vec![0u64; b_i64.len()];
+        modulus.reduce_i64_vec(&mut out, &b_i64);
+        for (o, b0) in izip!(out.iter(), b_i64.iter()) {
+            assert_eq!(*o as i128, (*b0 as i128).rem_euclid(p as i128));
+        }
+
+        let mut centered = vec![0i64; 3];
+        modulus.to_centered_i64_vec(&mut centered, &[p - 1, p >> 1, (p >> 1) + 1]);
+        assert_eq!(
+            centered,
+            vec![
+                -1,
+                (p >> 1) as i64,
+                ((p >> 1) as i128 + 1 - p as i128) as i64
+            ]
+        );
+    }
+}
@@ -1,19 +1,52 @@
-use itertools::Itertools;
-use rand::thread_rng;
+use itertools::{izip, Itertools};
+use rand::{thread_rng, RngCore};
 
-use crate::{core_crypto::modulus::ShoupRepresentationFq, utils::mod_inverse};
+use crate::{
+    core_crypto::modulus::ShoupRepresentationFq,
+    utils::{mod_exponent, mod_inverse},
+};
 
 use super::{
-    modulus::{BarrettBackend, ModulusBackendConfig, NativeModulusBackend},
-    prime::find_primitive_root,
+    modulus::{
+        BarrettBackend, ModulusBackendConfig, NativeModulusBackend, SpecialFormModulusBackend,
+    },
+    prime::{find_primitive_root, find_primitive_root_deterministic, is_prime},
 };
 
+/// Constant `value` < q paired with its shoup representation `shoup` = floor(value * 2^64 / q)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShoupConstant {
+    pub value: u64,
+    pub shoup: u64,
+}
+
+impl ShoupConstant {
+    pub fn new(value: u64, q: u64) -> ShoupConstant {
+        ShoupConstant {
+            value,
+            shoup: value.shoup_representation_fq(q),
+        }
+    }
+}
+
+/// Shoup's multiplication of `a` by `w` given shoup representation `w_shoup` of w. Outputs aw
+/// (mod q) in [0, 2q) for any a < 2^64.
+#[inline]
+fn shoup_mul_lazy(a: u64, w: u64, w_shoup: u64, q: u64) -> u64 {
+    let k = ((w_shoup as u128 * a as u128) >> 64) as u64;
+    w.wrapping_mul(a).wrapping_sub(k.wrapping_mul(q))
+}
+
 /// Forward butterfly routine for Number theoretic transform. Given inputs `x < 4q` and `y < 4q` mutates x and y in place to equal x' and y' such that
 /// x' = x + wy
 /// y' = x - wy
 /// where both x' and y' are in range [0, 4q)
 ///
 /// We implement Algorithm 4 of [FASTER ARITHMETIC FOR NUMBER-THEORETIC TRANSFORMS](https://arxiv.org/pdf/1205.2926.pdf)
+///
+/// # Safety
+///
+/// `x` and `y` must be valid for reads and writes and must not alias.
 pub unsafe fn forward_butterly(
     x: *mut u64,
     y: *mut u64,
@@ -26,15 +59,14 @@ pub unsafe fn forward_butterly(
     debug_assert!(*y < *q * 4, "{} >= (4q){}", *y, 4 * q);
 
     if *x >= *q_twice {
-        *x = *x - q_twice;
+        *x -= q_twice;
     }
 
     // TODO (Jay): Hot path expected. How expensive is it?
-    let k = ((*w_shoup as u128 * *y as u128) >> 64) as u64;
-    let t = w.wrapping_mul(*y).wrapping_sub(k.wrapping_mul(*q));
+    let t = shoup_mul_lazy(*y, *w, *w_shoup, *q);
 
     *y = *x + q_twice - t;
-    *x = *x + t;
+    *x += t;
 }
 
 /// Inverse butterfly routine of Inverse Number theoretic transform. Given inputs `x < 2q` and `y < 2q` mutates x and y to equal x' and y' such that
@@ -43,6 +75,10 @@ pub unsafe fn forward_butterly(
 /// where x' and y' in range [0, 2q)
 ///
 /// We implement Algorithm 3 of [FASTER ARITHMETIC FOR NUMBER-THEORETIC TRANSFORMS](https://arxiv.org/pdf/1205.2926.pdf)
+///
+/// # Safety
+///
+/// `x` and `y` must be valid for reads and writes and must not alias.
 pub unsafe fn inverse_butterfly(
     x: *mut u64,
     y: *mut u64,
@@ -60,12 +96,148 @@ pub unsafe fn inverse_butterfly(
     }
 
     let t = *x + q_twice - *y;
-    let k = ((*w_inv_shoup as u128 * t as u128) >> 64) as u64; // TODO (Jay): Hot path
-    *y = w_inv.wrapping_mul(t).wrapping_sub(k.wrapping_mul(*q));
+    *y = shoup_mul_lazy(t, *w_inv, *w_inv_shoup, *q); // TODO (Jay): Hot path
 
     *x = x_dash;
 }
 
+/// Radix-4 forward butterfly routine that merges two layers of forward NTT. Given inputs
+/// `x_i < 4q` mutates them in place to equal outputs of
+/// - layer 1: [forward_butterly] on (x_0, x_2) and (x_1, x_3) with `w[0]`
+/// - layer 2: [forward_butterly] on (x_0, x_1) with `w[1]` and (x_2, x_3) with `w[2]`
+///
+/// Thus outputs are bit-identical to two radix-2 layers, but every element is loaded and stored
+/// once.
+///
+/// # Safety
+///
+/// `x` must be valid for reads and writes and must not alias.
+#[inline]
+pub unsafe fn forward_butterfly_radix4(
+    x: [*mut u64; 4],
+    w: &[u64; 3],
+    w_shoup: &[u64; 3],
+    q: &u64,
+    q_twice: &u64,
+) {
+    let [mut x0, mut x1, mut x2, mut x3] = x.map(|x_i| *x_i);
+
+    forward_butterly(&mut x0, &mut x2, &w[0], &w_shoup[0], q, q_twice);
+    forward_butterly(&mut x1, &mut x3, &w[0], &w_shoup[0], q, q_twice);
+    forward_butterly(&mut x0, &mut x1, &w[1], &w_shoup[1], q, q_twice);
+    forward_butterly(&mut x2, &mut x3, &w[2], &w_shoup[2], q, q_twice);
+
+    izip!(x, [x0, x1, x2, x3]).for_each(|(x_i, v)| *x_i = v);
+}
+
+/// Radix-4 inverse butterfly routine that merges two layers of inverse NTT. Given inputs
+/// `x_i < 2q` mutates them in place to equal outputs of
+/// - layer 1: [inverse_butterfly] on (x_0, x_1) with `w_inv[0]` and (x_2, x_3) with `w_inv[1]`
+/// - layer 2: [inverse_butterfly] on (x_0, x_2) and (x_1, x_3) with `w_inv[2]`
+///
+/// # Safety
+///
+/// `x` must be valid for reads and writes and must not alias.
+#[inline]
+pub unsafe fn inverse_butterfly_radix4(
+    x: [*mut u64; 4],
+    w_inv: &[u64; 3],
+    w_inv_shoup: &[u64; 3],
+    q: &u64,
+    q_twice: &u64,
+) {
+    let [mut x0, mut x1, mut x2, mut x3] = x.map(|x_i| *x_i);
+
+    inverse_butterfly(&mut x0, &mut x1, &w_inv[0], &w_inv_shoup[0], q, q_twice);
+    inverse_butterfly(&mut x2, &mut x3, &w_inv[1], &w_inv_shoup[1], q, q_twice);
+    inverse_butterfly(&mut x0, &mut x2, &w_inv[2], &w_inv_shoup[2], q, q_twice);
+    inverse_butterfly(&mut x1, &mut x3, &w_inv[2], &w_inv_shoup[2], q, q_twice);
+
+    izip!(x, [x0, x1, x2, x3]).for_each(|(x_i, v)| *x_i = v);
+}
+
+/// Applies layer of forward NTT with `m` groups, each with butterflies at distance `t`
+fn forward_layer(
+    a: &mut [u64],
+    psi: &[u64],
+    psi_shoup: &[u64],
+    m: usize,
+    t: usize,
+    q: u64,
+    q_twice: u64,
+) {
+    for i in 0..m {
+        let j_1 = 2 * i * t;
+        let j_2 = j_1 + t;
+
+        unsafe {
+            let w = psi.get_unchecked(m + i);
+            let w_shoup = psi_shoup.get_unchecked(m + i);
+            for j in j_1..j_2 {
+                let x = a.get_unchecked_mut(j) as *mut u64;
+                let y = a.get_unchecked_mut(j + t) as *mut u64;
+                forward_butterly(x, y, w, w_shoup, &q, &q_twice);
+            }
+        }
+    }
+}
+
+/// Applies layer of inverse NTT with `h` groups, each with butterflies at distance `t`
+fn inverse_layer(
+    a: &mut [u64],
+    psi_inv: &[u64],
+    psi_inv_shoup: &[u64],
+    h: usize,
+    t: usize,
+    q: u64,
+    q_twice: u64,
+) {
+    let mut j_1: usize = 0;
+    for i in 0..h {
+        let j_2 = j_1 + t;
+        unsafe {
+            let w_inv = psi_inv.get_unchecked(h + i);
+            let w_inv_shoup = psi_inv_shoup.get_unchecked(h + i);
+
+            for j in j_1..j_2 {
+                let x = a.get_unchecked_mut(j) as *mut u64;
+                let y = a.get_unchecked_mut(j + t) as *mut u64;
+                inverse_butterfly(x, y, w_inv, w_inv_shoup, &q, &q_twice);
+            }
+        }
+        j_1 += 2 * t;
+    }
+}
+
+/// Applies last layer of inverse NTT, which also scales by n^{-1} (see [ntt_inv_lazy])
+fn inverse_last_layer(
+    a: &mut [u64],
+    n_inv: ShoupConstant,
+    n_inv_w: ShoupConstant,
+    q: u64,
+    q_twice: u64,
+) {
+    // Shoup's multiplication accepts x + y < 4q and outputs in [0, 2q)
+    let (a_lo, a_hi) = a.split_at_mut(a.len() / 2);
+    izip!(a_lo.iter_mut(), a_hi.iter_mut()).for_each(|(x, y)| {
+        debug_assert!(*x < q_twice, "{} >= (2q){q_twice}", *x);
+        debug_assert!(*y < q_twice, "{} >= (2q){q_twice}", *y);
+
+        let x_dash = shoup_mul_lazy(*x + *y, n_inv.value, n_inv.shoup, q);
+        *y = shoup_mul_lazy(*x + q_twice - *y, n_inv_w.value, n_inv_w.shoup, q);
+        *x = x_dash;
+    });
+}
+
+/// Reduces a_i in [0, 4q) to [0, 2q)
+fn reduce_from_4q(a: &mut [u64], q_twice: u64) {
+    a.iter_mut().for_each(|a0| {
+        if *a0 >= q_twice {
+            *a0 -= q_twice
+        }
+    });
+}
+
 /// Calculates forward number theoretic transform of vector `a`. We implement forward Cooley-tukey
 /// based forward NTT as outlined in Algorithm 1 of https://eprint.iacr.org/2016/504.pdf.
 ///
@@ -79,168 +251,769 @@ pub fn ntt(a: &mut [u64], psi: &[u64], psi_shoup: &[u64], q: u64, q_twice: u64)
     let mut m = 1;
     while m < n {
         t >>= 1;
+        forward_layer(a, psi, psi_shoup, m, t, q, q_twice);
+        m <<= 1;
+    }
+
+    reduce_from_4q(a, q_twice);
+}
+
+/// Same as [ntt] but merges pairs of layers using [forward_butterfly_radix4]. If number of layers
+/// is odd, first layer is radix-2.
+///
+/// Outputs are bit-identical to [ntt].
+pub fn ntt_radix4(a: &mut [u64], psi: &[u64], psi_shoup: &[u64], q: u64, q_twice: u64) {
+    debug_assert!(a.len() == psi.len());
+
+    let n = a.len();
+    let mut t = n;
 
+    let mut m = 1;
+    if n.trailing_zeros() % 2 == 1 {
+        t >>= 1;
+        forward_layer(a, psi, psi_shoup, m, t, q, q_twice);
+        m <<= 1;
+    }
+
+    while m < n {
+        // butterflies are at distance 2t in layer m and at distance t in layer 2m
+        t >>= 2;
         for i in 0..m {
-            let j_1 = 2 * i * t;
-            let j_2 = j_1 + t;
+            let j_1 = 4 * i * t;
+            unsafe {
+                let w = [
+                    *psi.get_unchecked(m + i),
+                    *psi.get_unchecked(2 * m + 2 * i),
+                    *psi.get_unchecked(2 * m + 2 * i + 1),
+                ];
+                let w_shoup = [
+                    *psi_shoup.get_unchecked(m + i),
+                    *psi_shoup.get_unchecked(2 * m + 2 * i),
+                    *psi_shoup.get_unchecked(2 * m + 2 * i + 1),
+                ];
+                let ptr = a.as_mut_ptr();
+                for j in j_1..j_1 + t {
+                    let x = [
+                        ptr.add(j),
+                        ptr.add(j + t),
+                        ptr.add(j + 2 * t),
+                        ptr.add(j + 3 * t),
+                    ];
+                    forward_butterfly_radix4(x, &w, &w_shoup, &q, &q_twice);
+                }
+            }
+        }
+        m <<= 2;
+    }
 
+    reduce_from_4q(a, q_twice);
+}
+
+/// Calculates inverse number theoretic transform of vector `a` with inputs in [0, 2q). We
+/// implement Gentleman-Sande based inverse NTT as outlined in Algorithm 2 of
+/// https://eprint.iacr.org/2016/504.pdf.
+///
+/// Instead of scaling every element by n^{-1} at the end, the last stage computes
+/// x' = n^{-1}(x + y) and y' = n^{-1}w(x - y) using Shoup's multiplication. `n_inv` is
+/// n^{-1} and `n_inv_w` is n^{-1}w, where w is the twiddle factor of the last stage.
+///
+/// Outputs INTT(a) where each element is in range [0,2q)
+pub fn ntt_inv_lazy(
+    a: &mut [u64],
+    psi_inv: &[u64],
+    psi_inv_shoup: &[u64],
+    n_inv: ShoupConstant,
+    n_inv_w: ShoupConstant,
+    q: u64,
+    q_twice: u64,
+) {
+    debug_assert!(a.len() == psi_inv.len());
+
+    let mut m = a.len();
+    let mut t = 1;
+    while m > 2 {
+        inverse_layer(a, psi_inv, psi_inv_shoup, m >> 1, t, q, q_twice);
+        t *= 2;
+        m >>= 1;
+    }
+
+    if m == 2 {
+        inverse_last_layer(a, n_inv, n_inv_w, q, q_twice);
+    }
+}
+
+/// Same as [ntt_inv_lazy] but merges pairs of layers, except the last one, using
+/// [inverse_butterfly_radix4]. If number of such layers is odd, first layer is radix-2.
+///
+/// Outputs are bit-identical to [ntt_inv_lazy].
+pub fn ntt_inv_radix4_lazy(
+    a: &mut [u64],
+    psi_inv: &[u64],
+    psi_inv_shoup: &[u64],
+    n_inv: ShoupConstant,
+    n_inv_w: ShoupConstant,
+    q: u64,
+    q_twice: u64,
+) {
+    debug_assert!(a.len() == psi_inv.len());
+
+    let mut m = a.len();
+    let mut t = 1;
+    if m.trailing_zeros().saturating_sub(1) % 2 == 1 {
+        inverse_layer(a, psi_inv, psi_inv_shoup, m >> 1, t, q, q_twice);
+        t *= 2;
+        m >>= 1;
+    }
+
+    while m > 2 {
+        // butterflies are at distance t in layer h and at distance 2t in layer h/2
+        let h = m >> 1;
+        for i in 0..h / 2 {
+            let j_1 = 4 * i * t;
             unsafe {
-                let w = psi.get_unchecked(m + i);
-                let w_shoup = psi_shoup.get_unchecked(m + i);
-                for j in j_1..j_2 {
-                    let x = a.get_unchecked_mut(j) as *mut u64;
-                    let y = a.get_unchecked_mut(j + t) as *mut u64;
-                    forward_butterly(x, y, w, w_shoup, &q, &q_twice);
+                let w_inv = [
+                    *psi_inv.get_unchecked(h + 2 * i),
+                    *psi_inv.get_unchecked(h + 2 * i + 1),
+                    *psi_inv.get_unchecked(h / 2 + i),
+                ];
+                let w_inv_shoup = [
+                    *psi_inv_shoup.get_unchecked(h + 2 * i),
+                    *psi_inv_shoup.get_unchecked(h + 2 * i + 1),
+                    *psi_inv_shoup.get_unchecked(h / 2 + i),
+                ];
+                let ptr = a.as_mut_ptr();
+                for j in j_1..j_1 + t {
+                    let x = [
+                        ptr.add(j),
+                        ptr.add(j + t),
+                        ptr.add(j + 2 * t),
+                        ptr.add(j + 3 * t),
+                    ];
+                    inverse_butterfly_radix4(x, &w_inv, &w_inv_shoup, &q, &q_twice);
                 }
             }
         }
+        t *= 4;
+        m >>= 2;
+    }
 
-        m <<= 1;
+    if m == 2 {
+        inverse_last_layer(a, n_inv, n_inv_w, q, q_twice);
     }
+}
 
+/// Reduces a_i in [0, 2q) to [0, q)
+fn reduce_from_2q(a: &mut [u64], q: u64) {
     a.iter_mut().for_each(|a0| {
-        if *a0 >= q_twice {
-            *a0 -= q_twice
+        if *a0 >= q {
+            *a0 -= q
         }
     });
 }
 
+/// Same as [ntt_inv_lazy] but outputs INTT(a) where each element is in range [0,q)
 pub fn ntt_inv(
     a: &mut [u64],
     psi_inv: &[u64],
     psi_inv_shoup: &[u64],
-    n_inv: u64,
+    n_inv: ShoupConstant,
+    n_inv_w: ShoupConstant,
     q: u64,
     q_twice: u64,
+) {
+    ntt_inv_lazy(a, psi_inv, psi_inv_shoup, n_inv, n_inv_w, q, q_twice);
+    reduce_from_2q(a, q);
+}
+
+/// Same as [ntt_inv_radix4_lazy] but outputs INTT(a) where each element is in range [0,q)
+pub fn ntt_inv_radix4(
+    a: &mut [u64],
+    psi_inv: &[u64],
+    psi_inv_shoup: &[u64],
+    n_inv: ShoupConstant,
+    n_inv_w: ShoupConstant,
+    q: u64,
+    q_twice: u64,
+) {
+    ntt_inv_radix4_lazy(a, psi_inv, psi_inv_shoup, n_inv, n_inv_w, q, q_twice);
+    reduce_from_2q(a, q);
+}
+
+/// Forward NTT of vector `a` same as [ntt] but with modular arithmetic of special form modulus.
+///
+/// Outputs NTT(a) where each element is in range [0,q)
+pub fn ntt_special_form(a: &mut [u64], psi: &[u64], modulus: &SpecialFormModulusBackend) {
+    debug_assert!(a.len() == psi.len());
+
+    let n = a.len();
+    let mut t = n;
+
+    let mut m = 1;
+    while m < n {
+        t >>= 1;
+
+        for i in 0..m {
+            let j_1 = 2 * i * t;
+            let j_2 = j_1 + t;
+
+            let w = psi[m + i];
+            for j in j_1..j_2 {
+                let wy = modulus.mul_mod(a[j + t], w);
+                a[j + t] = modulus.sub_mod(a[j], wy);
+                a[j] = modulus.add_mod(a[j], wy);
+            }
+        }
+
+        m <<= 1;
+    }
+}
+
+/// Inverse NTT of vector `a` same as [ntt_inv] but with modular arithmetic of special form modulus.
+///
+/// Outputs INTT(a) where each element is in range [0,q)
+pub fn ntt_inv_special_form(
+    a: &mut [u64],
+    psi_inv: &[u64],
+    n_inv: u64,
+    modulus: &SpecialFormModulusBackend,
 ) {
     debug_assert!(a.len() == psi_inv.len());
 
     let mut m = a.len();
     let mut t = 1;
-    while m > 1 {
+    while m > 2 {
         let mut j_1: usize = 0;
         let h = m >> 1;
         for i in 0..h {
             let j_2 = j_1 + t;
-            unsafe {
-                let w_inv = psi_inv.get_unchecked(h + i);
-                let w_inv_shoup = psi_inv_shoup.get_unchecked(h + i);
-
-                for j in j_1..j_2 {
-                    let x = a.get_unchecked_mut(j) as *mut u64;
-                    let y = a.get_unchecked_mut(j + t) as *mut u64;
-                    inverse_butterfly(x, y, w_inv, w_inv_shoup, &q, &q_twice);
-                }
+            let w_inv = psi_inv[h + i];
+            for j in j_1..j_2 {
+                let x = a[j];
+                let y = a[j + t];
+                a[j] = modulus.add_mod(x, y);
+                a[j + t] = modulus.mul_mod(modulus.sub_mod(x, y), w_inv);
             }
-            j_1 = j_1 + 2 * t;
+            j_1 += 2 * t;
         }
         t *= 2;
         m >>= 1;
     }
 
-    a.iter_mut()
-        .for_each(|a0| *a0 = ((*a0 as u128 * n_inv as u128) % q as u128) as u64);
+    // Last stage scales by n^{-1}
+    if m == 2 {
+        let n_inv_w = modulus.mul_mod(n_inv, psi_inv[1]);
+        let (a_lo, a_hi) = a.split_at_mut(t);
+        izip!(a_lo.iter_mut(), a_hi.iter_mut()).for_each(|(x, y)| {
+            let x_dash = modulus.mul_mod(modulus.add_mod(*x, *y), n_inv);
+            *y = modulus.mul_mod(modulus.sub_mod(*x, *y), n_inv_w);
+            *x = x_dash;
+        });
+    }
+}
+
+/// Ring over which NTT multiplies polynomials
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NttMode {
+    /// `Z_q[X]/(X^n + 1)`, requires primitive 2n^{th} root of unity \psi
+    Negacyclic,
+    /// `Z_q[X]/(X^n - 1)`, requires primitive n^{th} root of unity \omega
+    Cyclic,
+}
+
+/// Butterfly kernels of [NativeNTTBackend]. Kernels produce bit-identical outputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NttKernel {
+    /// One radix-2 layer at a time ([ntt], [ntt_inv])
+    Radix2,
+    /// Two merged layers at a time ([ntt_radix4], [ntt_inv_radix4]). Requires fewer passes over
+    /// the input, thus is expected to be faster for large n.
+    Radix4,
+}
+
+/// Number theoretic transform of `Z_q[X]/(X^n + 1)` or `Z_q[X]/(X^n - 1)` (see [NttMode]).
+///
+/// NTT domain values are in bit reversed order, that is i^{th} output is evaluation at
+/// \psi^{2 brv(i) + 1} in negacyclic mode and at \omega^{brv(i)} in cyclic mode, where \psi (resp.
+/// \omega) is the primitive 2n^{th} (resp. n^{th}) root of unity of the backend. Thus backends
+/// agree on NTT domain values only if they use the same root.
+pub trait NttBackend {
+    fn modulus(&self) -> u64;
+
+    /// Ring degree n
+    fn ring_size(&self) -> u64;
+
+    /// Forward NTT of `a` with inputs in [0, q). Outputs are in [0, q).
+    fn forward(&self, a: &mut [u64]);
+
+    /// Same as `forward` but outputs are in [0, 2q). Defaults to `forward`.
+    fn forward_lazy(&self, a: &mut [u64]) {
+        self.forward(a)
+    }
+
+    /// Inverse NTT of `a` with inputs in [0, q). Outputs are in [0, q).
+    fn inverse(&self, a: &mut [u64]);
+
+    /// Same as `inverse` but outputs are in [0, 2q). Defaults to `inverse`.
+    fn inverse_lazy(&self, a: &mut [u64]) {
+        self.inverse(a)
+    }
 }
 
+/// NTT backend for negacyclic ring `Z_q[X]/(X^n + 1)` or, in cyclic mode, `Z_q[X]/(X^n - 1)`.
+///
+/// Moduli of at most 60 bits use Shoup butterflies with lazy reduction. Larger moduli are
+/// supported only if they are of special form (for ex, goldilocks prime), in which case
+/// butterflies use [SpecialFormModulusBackend].
+///
+/// Both modes share the butterfly kernels and only differ in twiddle factors. In negacyclic mode
+/// twiddle at index k is \psi^{brv(k)}. Cyclic mode drops the twist by \psi, that is twiddle at
+/// index k = m + i of stage m is \omega^{brv_{log m}(i) n / 2m}.
 pub struct NativeNTTBackend {
     q: u64,
-    q_twice: u64,
     n: u64,
-    n_inv: u64,
+    n_inv: ShoupConstant,
+    /// n^{-1}w, where w is twiddle factor of last inverse stage
+    n_inv_w: ShoupConstant,
+    mode: NttMode,
+    kernel: NttKernel,
+    /// Twiddle factors in bit reversed order
     psi_powers_bo: Box<[u64]>,
     psi_inv_powers_bo: Box<[u64]>,
     psi_powers_bo_shoup: Box<[u64]>,
     psi_inv_powers_bo_shoup: Box<[u64]>,
+    /// 2q, set only if butterflies use Shoup's multiplication (2q overflows for special form
+    /// moduli exceeding 63 bits)
+    q_twice: Option<u64>,
+    /// Set if butterflies use special form arithmetic instead of Shoup's
+    special_form: Option<SpecialFormModulusBackend>,
+}
+
+/// Selects primitive root of unity of [NativeNTTBackend], that is 2n^{th} root \psi in negacyclic
+/// mode and n^{th} root \omega in cyclic mode
+pub enum RootSelection<'a> {
+    /// Draws root using `thread_rng`, thus NTT domain values are specific to the instance
+    Random,
+    /// Draws root using the rng. NTT tables are reproducible for a seeded rng.
+    WithRng(&'a mut dyn RngCore),
+    /// Uses smallest primitive root of unity (see [find_primitive_root_deterministic]). NTT tables
+    /// are identical across runs and machines.
+    Deterministic,
+}
+
+/// Builder for [NativeNTTBackend]. Use `NativeNTTBackend::builder` to create one.
+pub struct NativeNTTBackendBuilder<'a> {
+    q: u64,
+    n: u64,
+    roots: RootSelection<'a>,
+    mode: NttMode,
+    kernel: NttKernel,
+    special_form: bool,
+}
+
+impl<'a> NativeNTTBackendBuilder<'a> {
+    /// Sets how root of unity is selected. Defaults to [RootSelection::Random].
+    pub fn roots(mut self, roots: RootSelection<'a>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// Sets ring of the transform. Defaults to [NttMode::Negacyclic].
+    pub fn mode(mut self, mode: NttMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets butterfly kernel. Defaults to [NttKernel::Radix2]. Kernel only affects Shoup
+    /// butterflies, special form moduli always use radix-2 layers.
+    pub fn kernel(mut self, kernel: NttKernel) -> Self {
+        self.kernel = kernel;
+        self
+    }
+
+    /// Uses special form arithmetic for any modulus of special form, including ones of at most 60
+    /// bits which otherwise use Shoup butterflies. `build` panics if modulus is not of special
+    /// form.
+    pub fn special_form(mut self) -> Self {
+        self.special_form = true;
+        self
+    }
+
+    /// Panics if `q` is not prime or `n` is not power of two greater than 1
+    pub fn build(self) -> NativeNTTBackend {
+        let (q, n) = (self.q, self.n);
+        assert!(
+            n.is_power_of_two() && n > 1,
+            "Ring degree {n} is not power of two > 1"
+        );
+        let special_form = if self.special_form {
+            assert!(is_prime(q), "Modulus {q} is not prime");
+            Some(
+                SpecialFormModulusBackend::try_initialise(q)
+                    .unwrap_or_else(|| panic!("Modulus {q} is not of special form")),
+            )
+        } else {
+            NativeNTTBackend::special_form_for(q)
+        };
+        let order = match self.mode {
+            NttMode::Negacyclic => n * 2,
+            NttMode::Cyclic => n,
+        };
+        let root = match self.roots {
+            RootSelection::Random => find_primitive_root(q, order, &mut thread_rng()),
+            RootSelection::WithRng(mut rng) => find_primitive_root(q, order, &mut rng),
+            RootSelection::Deterministic => Some(find_primitive_root_deterministic(q, order)),
+        }
+        .unwrap_or_else(|| panic!("Unable to find {order}^th root of unity"));
+        NativeNTTBackend::new_with_root(q, n, root, self.mode, self.kernel, special_form)
+    }
 }
 
 impl NativeNTTBackend {
+    /// Panics if `q` is not prime
+    ///
+    /// 2n^{th} root of unity \psi is drawn at random, thus NTT domain values are specific to the
+    /// instance. Use `builder` with [RootSelection] for reproducible NTT tables.
     pub fn new(q: u64, n: u64) -> NativeNTTBackend {
-        // \psi = 2n^{th} primitive root of unity in F_q
-        let mut rng = thread_rng();
-        let psi =
-            find_primitive_root(q, n * 2, &mut rng).expect("Unable to find 2n^th root of unity");
-        let psi_inv = mod_inverse(psi, q);
-
-        let modulus = NativeModulusBackend::initialise(q);
-
-        let mut psi_powers = Vec::with_capacity(n as usize);
-        let mut psi_inv_powers = Vec::with_capacity(n as usize);
-        let mut running_psi = 1;
-        let mut running_psi_inv = 1;
+        NativeNTTBackend::builder(q, n).build()
+    }
+
+    /// Returns builder for backend of modulus `q` and ring degree `n`
+    pub fn builder<'a>(q: u64, n: u64) -> NativeNTTBackendBuilder<'a> {
+        NativeNTTBackendBuilder {
+            q,
+            n,
+            roots: RootSelection::Random,
+            mode: NttMode::Negacyclic,
+            kernel: NttKernel::Radix2,
+            special_form: false,
+        }
+    }
+
+    /// Checks that `q` is prime and returns special form backend if `q` exceeds 60 bits
+    fn special_form_for(q: u64) -> Option<SpecialFormModulusBackend> {
+        assert!(is_prime(q), "Modulus {q} is not prime");
+        if q >> 60 == 0 {
+            None
+        } else {
+            Some(
+                SpecialFormModulusBackend::try_initialise(q).unwrap_or_else(|| {
+                    panic!("Modulus {q} exceeds 60 bits and is not of special form")
+                }),
+            )
+        }
+    }
+
+    /// Returns backend with `root` as primitive 2n^{th} (negacyclic) or n^{th} (cyclic) root of
+    /// unity
+    fn new_with_root(
+        q: u64,
+        n: u64,
+        root: u64,
+        mode: NttMode,
+        kernel: NttKernel,
+        special_form: Option<SpecialFormModulusBackend>,
+    ) -> NativeNTTBackend {
+        let root_inv = mod_inverse(root, q);
+
+        let barrett = special_form
+            .is_none()
+            .then(|| NativeModulusBackend::initialise(q));
+        let mul_mod = |a: u64, b: u64| match (&special_form, &barrett) {
+            (Some(modulus), _) => modulus.mul_mod(a, b),
+            (_, Some(modulus)) => modulus.mul_mod_fast(a, b),
+            _ => unreachable!(),
+        };
+
+        let mut root_powers = Vec::with_capacity(n as usize);
+        let mut root_inv_powers = Vec::with_capacity(n as usize);
+        let mut running_root = 1;
+        let mut running_root_inv = 1;
         for _ in 0..n {
-            psi_powers.push(running_psi);
-            psi_inv_powers.push(running_psi_inv);
+            root_powers.push(running_root);
+            root_inv_powers.push(running_root_inv);
 
-            running_psi = modulus.mul_mod_fast(running_psi, psi);
-            running_psi_inv = modulus.mul_mod_fast(running_psi_inv, psi_inv);
+            running_root = mul_mod(running_root, root);
+            running_root_inv = mul_mod(running_root_inv, root_inv);
         }
 
-        // powers stored in bit reversed order
+        // twiddles stored in bit reversed order
         let mut psi_powers_bo = vec![0u64; n as usize];
         let mut psi_inv_powers_bo = vec![0u64; n as usize];
         let shift_by = n.leading_zeros() + 1;
-        for i in 0..n as usize {
-            // i in bit reversed order
-            let bo_index = i.reverse_bits() >> shift_by;
+        for k in 0..n as usize {
+            // k in bit reversed order
+            let bo_index = k.reverse_bits() >> shift_by;
+            let exponent = match mode {
+                NttMode::Negacyclic => bo_index,
+                // For k = m + i, brv(k) = (2 brv_{log m}(i) + 1) n / 2m. Clearing the lowest set
+                // bit and halving gives brv_{log m}(i) n / 2m.
+                NttMode::Cyclic => (bo_index & bo_index.wrapping_sub(1)) >> 1,
+            };
 
-            psi_powers_bo[bo_index] = psi_powers[i];
-            psi_inv_powers_bo[bo_index] = psi_inv_powers[i];
+            psi_powers_bo[k] = root_powers[exponent];
+            psi_inv_powers_bo[k] = root_inv_powers[exponent];
         }
 
-        // shoup representation
-        let psi_powers_bo_shoup = psi_powers_bo
-            .iter()
-            .map(|v| v.shoup_representation_fq(q))
-            .collect_vec();
-        let psi_inv_powers_bo_shoup = psi_inv_powers_bo
-            .iter()
-            .map(|v| v.shoup_representation_fq(q))
-            .collect_vec();
+        // shoup representation, only required by Shoup butterflies
+        let (psi_powers_bo_shoup, psi_inv_powers_bo_shoup) = if special_form.is_none() {
+            (
+                psi_powers_bo
+                    .iter()
+                    .map(|v| v.shoup_representation_fq(q))
+                    .collect_vec(),
+                psi_inv_powers_bo
+                    .iter()
+                    .map(|v| v.shoup_representation_fq(q))
+                    .collect_vec(),
+            )
+        } else {
+            (vec![], vec![])
+        };
 
-        // n^{-1} \mod{q}
+        // n^{-1} \mod{q}, and n^{-1}w for last inverse stage
         let n_inv = mod_inverse(n, q);
+        let n_inv_w = mul_mod(n_inv, psi_inv_powers_bo[1]);
 
         NativeNTTBackend {
             q,
-            q_twice: 2 * q,
             n,
-            n_inv,
+            n_inv: ShoupConstant::new(n_inv, q),
+            n_inv_w: ShoupConstant::new(n_inv_w, q),
+            mode,
+            kernel,
             psi_powers_bo: psi_powers_bo.into_boxed_slice(),
             psi_inv_powers_bo: psi_inv_powers_bo.into_boxed_slice(),
             psi_powers_bo_shoup: psi_powers_bo_shoup.into_boxed_slice(),
             psi_inv_powers_bo_shoup: psi_inv_powers_bo_shoup.into_boxed_slice(),
+            q_twice: special_form.is_none().then(|| 2 * q),
+            special_form,
         }
     }
 
+    /// Forward NTT with outputs in [0, q). Same as [NttBackend::forward]
     pub fn ntt(&self, a: &mut [u64]) {
+        self.forward(a)
+    }
+
+    /// Inverse NTT with outputs in [0, q). Same as [NttBackend::inverse]
+    pub fn ntt_inv(&self, a: &mut [u64]) {
+        self.inverse(a)
+    }
+
+    pub fn mode(&self) -> NttMode {
+        self.mode
+    }
+
+    pub fn kernel(&self) -> NttKernel {
+        self.kernel
+    }
+}
+
+impl NttBackend for NativeNTTBackend {
+    fn modulus(&self) -> u64 {
+        self.q
+    }
+
+    fn ring_size(&self) -> u64 {
+        self.n
+    }
+
+    fn forward(&self, a: &mut [u64]) {
+        self.forward_lazy(a);
+        if self.special_form.is_none() {
+            a.iter_mut().for_each(|a0| {
+                if *a0 >= self.q {
+                    *a0 -= self.q
+                }
+            });
+        }
+    }
+
+    fn forward_lazy(&self, a: &mut [u64]) {
+        debug_assert!(a.len() == self.n as usize);
+        if let Some(modulus) = &self.special_form {
+            return ntt_special_form(a, &self.psi_powers_bo, modulus);
+        }
+        let ntt = match self.kernel {
+            NttKernel::Radix2 => ntt,
+            NttKernel::Radix4 => ntt_radix4,
+        };
+        let q_twice = self.q_twice.expect("2q is set for Shoup butterflies");
         ntt(
             a,
             &self.psi_powers_bo,
             &self.psi_powers_bo_shoup,
             self.q,
-            self.q_twice,
+            q_twice,
         );
     }
 
-    pub fn ntt_inv(&self, a: &mut [u64]) {
+    fn inverse(&self, a: &mut [u64]) {
+        debug_assert!(a.len() == self.n as usize);
+        if let Some(modulus) = &self.special_form {
+            return ntt_inv_special_form(a, &self.psi_inv_powers_bo, self.n_inv.value, modulus);
+        }
+        let ntt_inv = match self.kernel {
+            NttKernel::Radix2 => ntt_inv,
+            NttKernel::Radix4 => ntt_inv_radix4,
+        };
+        let q_twice = self.q_twice.expect("2q is set for Shoup butterflies");
         ntt_inv(
             a,
             &self.psi_inv_powers_bo,
             &self.psi_inv_powers_bo_shoup,
             self.n_inv,
+            self.n_inv_w,
+            self.q,
+            q_twice,
+        );
+    }
+
+    fn inverse_lazy(&self, a: &mut [u64]) {
+        debug_assert!(a.len() == self.n as usize);
+        if let Some(modulus) = &self.special_form {
+            return ntt_inv_special_form(a, &self.psi_inv_powers_bo, self.n_inv.value, modulus);
+        }
+        let ntt_inv_lazy = match self.kernel {
+            NttKernel::Radix2 => ntt_inv_lazy,
+            NttKernel::Radix4 => ntt_inv_radix4_lazy,
+        };
+        let q_twice = self.q_twice.expect("2q is set for Shoup butterflies");
+        ntt_inv_lazy(
+            a,
+            &self.psi_inv_powers_bo,
+            &self.psi_inv_powers_bo_shoup,
+            self.n_inv,
+            self.n_inv_w,
             self.q,
-            self.q_twice,
+            q_twice,
         );
     }
 }
 
+/// Reference NTT backend that evaluates and interpolates in O(n^2) using u128 arithmetic.
+///
+/// Slow, but simple enough to be obviously correct. Meant for testing other backends with the
+/// same \psi.
+pub struct ReferenceNttBackend {
+    q: u64,
+    n: u64,
+    n_inv: u64,
+    /// Evaluation points \psi^{2 brv(i) + 1} (negacyclic) or \omega^{brv(i)} (cyclic) for i \in [0, n)
+    roots: Box<[u64]>,
+    /// Inverses of `roots`
+    roots_inv: Box<[u64]>,
+}
+
+impl ReferenceNttBackend {
+    /// Returns backend with \psi as 2n^{th} primitive root of unity. Panics if `psi` is not one.
+    pub fn new(q: u64, n: u64, psi: u64) -> ReferenceNttBackend {
+        assert!(
+            mod_exponent(psi, n, q) == q - 1,
+            "{psi} is not primitive 2n^th root of unity"
+        );
+        ReferenceNttBackend::new_with_root(q, n, psi, NttMode::Negacyclic)
+    }
+
+    /// Returns backend in cyclic mode with \omega as n^{th} primitive root of unity. Panics if
+    /// `omega` is not one.
+    pub fn new_cyclic(q: u64, n: u64, omega: u64) -> ReferenceNttBackend {
+        assert!(
+            n > 1 && mod_exponent(omega, n / 2, q) == q - 1,
+            "{omega} is not primitive n^th root of unity"
+        );
+        ReferenceNttBackend::new_with_root(q, n, omega, NttMode::Cyclic)
+    }
+
+    fn new_with_root(q: u64, n: u64, root: u64, mode: NttMode) -> ReferenceNttBackend {
+        assert!(is_prime(q), "Modulus {q} is not prime");
+        assert!(n.is_power_of_two(), "{n} is not power of two");
+
+        let root_inv = mod_inverse(root, q);
+        let shift_by = n.leading_zeros() + 1;
+        let (roots, roots_inv) = (0..n as usize)
+            .map(|i| {
+                let brv_i = (i.reverse_bits() >> shift_by) as u64;
+                let k = match mode {
+                    NttMode::Negacyclic => 2 * brv_i + 1,
+                    NttMode::Cyclic => brv_i,
+                };
+                (mod_exponent(root, k, q), mod_exponent(root_inv, k, q))
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        ReferenceNttBackend {
+            q,
+            n,
+            n_inv: mod_inverse(n, q),
+            roots: roots.into_boxed_slice(),
+            roots_inv: roots_inv.into_boxed_slice(),
+        }
+    }
+
+    /// Uses the same \psi as [NativeNTTBackend] with [RootSelection::Deterministic], thus both
+    /// backends have identical NTT domain values.
+    pub fn new_deterministic(q: u64, n: u64) -> ReferenceNttBackend {
+        ReferenceNttBackend::new(q, n, find_primitive_root_deterministic(q, n * 2))
+    }
+
+    /// Returns a(x) for each x in `points`, scaled by `scale`
+    fn evaluate(&self, a: &[u64], points: &[u64], scale: u64) -> Vec<u64> {
+        let q = self.q as u128;
+        points
+            .iter()
+            .map(|x| {
+                // Horner's method
+                let a_x = a
+                    .iter()
+                    .rev()
+                    .fold(0u128, |acc, a0| (acc * *x as u128 + *a0 as u128) % q);
+                ((a_x * scale as u128) % q) as u64
+            })
+            .collect()
+    }
+}
+
+impl NttBackend for ReferenceNttBackend {
+    fn modulus(&self) -> u64 {
+        self.q
+    }
+
+    fn ring_size(&self) -> u64 {
+        self.n
+    }
+
+    fn forward(&self, a: &mut [u64]) {
+        debug_assert!(a.len() == self.n as usize);
+        let a_ntt = self.evaluate(a, &self.roots, 1);
+        a.copy_from_slice(&a_ntt);
+    }
+
+    /// a_j = n^{-1} \sum_i \hat{a}_i x_i^{-j}, where x_i are evaluation points
+    fn inverse(&self, a: &mut [u64]) {
+        debug_assert!(a.len() == self.n as usize);
+        let q = self.q as u128;
+        let mut out = vec![0u128; a.len()];
+        for (a_i, root_inv) in a.iter().zip(self.roots_inv.iter()) {
+            let mut root_inv_j = *a_i as u128;
+            for out_j in out.iter_mut() {
+                *out_j = (*out_j + root_inv_j) % q;
+                root_inv_j = (root_inv_j * *root_inv as u128) % q;
+            }
+        }
+        izip!(a.iter_mut(), out.iter())
+            .for_each(|(a0, out0)| *a0 = ((*out0 * self.n_inv as u128) % q) as u64);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core_crypto::num::UnsignedInteger;
-    use rand::{distributions::Uniform, Rng};
+    use crate::core_crypto::{modulus::GOLDILOCKS_PRIME, num::UnsignedInteger};
+    use rand::{distributions::Uniform, rngs::StdRng, Rng, SeedableRng};
 
     const Q_60_BITS: u64 = 1152921504606748673;
+    const Q_SOLINAS_59_BITS: u64 = (1 << 59) - (1 << 28) + 1;
     const N: u64 = 1 << 4;
 
     const K: usize = 128;
@@ -268,4 +1041,376 @@ mod tests {
             assert_eq!(a, a_clone);
         }
     }
+
+    /// Returns a * b in Z_q[X]/(X^n + 1)
+    fn negacyclic_mul_naive(a: &[u64], b: &[u64], q: u64) -> Vec<u64> {
+        let n = a.len();
+        let q = q as u128;
+        let mut c = vec![0u128; n];
+        for i in 0..n {
+            for j in 0..n {
+                let ab = (a[i] as u128 * b[j] as u128) % q;
+                if i + j < n {
+                    c[i + j] = (c[i + j] + ab) % q;
+                } else {
+                    c[i + j - n] = (c[i + j - n] + q - ab) % q;
+                }
+            }
+        }
+        c.iter().map(|c0| *c0 as u64).collect_vec()
+    }
+
+    #[test]
+    fn native_ntt_backend_special_form_works() {
+        for ntt_backend in [
+            NativeNTTBackend::new(GOLDILOCKS_PRIME, N),
+            NativeNTTBackend::builder(Q_SOLINAS_59_BITS, N)
+                .special_form()
+                .build(),
+            NativeNTTBackend::builder(Q_60_BITS, N)
+                .special_form()
+                .build(),
+            NativeNTTBackend::new(Q_60_BITS, N),
+        ] {
+            let q = ntt_backend.q;
+            for _ in 0..K {
+                let a = random_vec_in_fq(N as usize, q);
+                let b = random_vec_in_fq(N as usize, q);
+
+                let mut a_ntt = a.clone();
+                let mut b_ntt = b.clone();
+                ntt_backend.ntt(&mut a_ntt);
+                ntt_backend.ntt(&mut b_ntt);
+                let mut c = izip!(a_ntt.iter(), b_ntt.iter())
+                    .map(|(a0, b0)| ((*a0 as u128 * *b0 as u128) % q as u128) as u64)
+                    .collect_vec();
+                ntt_backend.ntt_inv(&mut c);
+
+                assert_eq!(c, negacyclic_mul_naive(&a, &b, q));
+            }
+        }
+    }
+
+    #[test]
+    fn native_ntt_backend_is_reproducible() {
+        let a = random_vec_in_fq(N as usize, Q_60_BITS);
+        let ntt_of = |ntt_backend: NativeNTTBackend| {
+            let mut a = a.clone();
+            ntt_backend.ntt(&mut a);
+            a
+        };
+
+        let new_deterministic = |q| {
+            NativeNTTBackend::builder(q, N)
+                .roots(RootSelection::Deterministic)
+                .build()
+        };
+        assert_eq!(
+            ntt_of(new_deterministic(Q_60_BITS)),
+            ntt_of(new_deterministic(Q_60_BITS))
+        );
+
+        let new_seeded = |seed| {
+            NativeNTTBackend::builder(Q_60_BITS, N)
+                .roots(RootSelection::WithRng(&mut StdRng::seed_from_u64(seed)))
+                .build()
+        };
+        assert_eq!(ntt_of(new_seeded(42)), ntt_of(new_seeded(42)));
+        assert_ne!(ntt_of(new_seeded(42)), ntt_of(new_seeded(43)));
+
+        // special form arithmetic with the same \psi gives identical outputs
+        assert_eq!(
+            ntt_of(
+                NativeNTTBackend::builder(Q_60_BITS, N)
+                    .roots(RootSelection::Deterministic)
+                    .special_form()
+                    .build()
+            ),
+            ntt_of(new_deterministic(Q_60_BITS))
+        );
+        assert_eq!(
+            ntt_of(
+                NativeNTTBackend::builder(Q_60_BITS, N)
+                    .roots(RootSelection::WithRng(&mut StdRng::seed_from_u64(42)))
+                    .special_form()
+                    .build()
+            ),
+            ntt_of(new_seeded(42))
+        );
+
+        // deterministic tables are valid
+        let ntt_backend = new_deterministic(GOLDILOCKS_PRIME);
+        let mut b = random_vec_in_fq(N as usize, GOLDILOCKS_PRIME);
+        let b_clone = b.clone();
+        ntt_backend.forward(&mut b);
+        ntt_backend.inverse(&mut b);
+        assert_eq!(b, b_clone);
+    }
+
+    /// Checks output ranges of `ntt_backend` and that it multiplies same as `mul_naive`
+    fn check_ntt_backend(
+        ntt_backend: &dyn NttBackend,
+        mul_naive: fn(&[u64], &[u64], u64) -> Vec<u64>,
+    ) {
+        let q = ntt_backend.modulus();
+        let n = ntt_backend.ring_size() as usize;
+        let lazy_bound = q as u128 * 2;
+        for _ in 0..K {
+            let a = random_vec_in_fq(n, q);
+            let b = random_vec_in_fq(n, q);
+
+            let mut a_ntt = a.clone();
+            let mut b_ntt = b.clone();
+            ntt_backend.forward(&mut a_ntt);
+            ntt_backend.forward_lazy(&mut b_ntt);
+            assert!(a_ntt.iter().all(|a0| *a0 < q));
+            assert!(b_ntt.iter().all(|b0| (*b0 as u128) < lazy_bound));
+
+            let mut a_lazy = a.clone();
+            ntt_backend.forward_lazy(&mut a_lazy);
+            assert!(izip!(a_lazy.iter(), a_ntt.iter()).all(|(l, r)| l % q == *r));
+
+            let c = izip!(a_ntt.iter(), b_ntt.iter())
+                .map(|(a0, b0)| ((*a0 as u128 * *b0 as u128) % q as u128) as u64)
+                .collect_vec();
+            let mut c_lazy = c.clone();
+            let mut c_reduced = c;
+            ntt_backend.inverse(&mut c_reduced);
+            ntt_backend.inverse_lazy(&mut c_lazy);
+            assert!(c_lazy.iter().all(|c0| (*c0 as u128) < lazy_bound));
+            assert!(izip!(c_lazy.iter(), c_reduced.iter()).all(|(l, r)| l % q == *r));
+            assert_eq!(c_reduced, mul_naive(&a, &b, q));
+        }
+    }
+
+    #[test]
+    fn ntt_backends_are_interchangeable() {
+        let backends: Vec<Box<dyn NttBackend>> = vec![
+            Box::new(NativeNTTBackend::new(Q_60_BITS, N)),
+            Box::new(NativeNTTBackend::new(GOLDILOCKS_PRIME, N)),
+            Box::new(
+                NativeNTTBackend::builder(Q_SOLINAS_59_BITS, N)
+                    .special_form()
+                    .build(),
+            ),
+            Box::new(ReferenceNttBackend::new_deterministic(Q_60_BITS, N)),
+            Box::new(ReferenceNttBackend::new_deterministic(GOLDILOCKS_PRIME, N)),
+        ];
+        backends
+            .iter()
+            .for_each(|b| check_ntt_backend(b.as_ref(), negacyclic_mul_naive));
+
+        // native backends agree with reference backend for same \psi
+        for (q, n) in [(Q_60_BITS, N), (Q_60_BITS, 1 << 10), (GOLDILOCKS_PRIME, N)] {
+            let native = NativeNTTBackend::builder(q, n)
+                .roots(RootSelection::Deterministic)
+                .build();
+            let reference = ReferenceNttBackend::new_deterministic(q, n);
+            let a = random_vec_in_fq(n as usize, q);
+            let mut a_native = a.clone();
+            let mut a_reference = a.clone();
+            native.forward(&mut a_native);
+            reference.forward(&mut a_reference);
+            assert_eq!(a_native, a_reference);
+
+            native.inverse(&mut a_native);
+            reference.inverse(&mut a_reference);
+            assert_eq!(a_native, a);
+            assert_eq!(a_reference, a);
+        }
+    }
+
+    /// Returns a * b in Z_q[X]/(X^n - 1)
+    fn cyclic_mul_naive(a: &[u64], b: &[u64], q: u64) -> Vec<u64> {
+        let n = a.len();
+        let q = q as u128;
+        let mut c = vec![0u128; n];
+        for i in 0..n {
+            for j in 0..n {
+                let ab = (a[i] as u128 * b[j] as u128) % q;
+                c[(i + j) % n] = (c[(i + j) % n] + ab) % q;
+            }
+        }
+        c.iter().map(|c0| *c0 as u64).collect_vec()
+    }
+
+    #[test]
+    fn native_ntt_backend_cyclic_works() {
+        // 257 - 1 = 256, thus 257 supports cyclic but not negacyclic NTT of size 256
+        for (q, n) in [
+            (Q_60_BITS, N),
+            (GOLDILOCKS_PRIME, N),
+            (Q_SOLINAS_59_BITS, 2),
+            (257, 256),
+        ] {
+            let omega = find_primitive_root_deterministic(q, n);
+            let native = NativeNTTBackend::builder(q, n)
+                .roots(RootSelection::Deterministic)
+                .mode(NttMode::Cyclic)
+                .build();
+            let reference = ReferenceNttBackend::new_cyclic(q, n, omega);
+            assert_eq!(native.mode(), NttMode::Cyclic);
+
+            check_ntt_backend(
+                &NativeNTTBackend::builder(q, n)
+                    .mode(NttMode::Cyclic)
+                    .build(),
+                cyclic_mul_naive,
+            );
+            check_ntt_backend(&native, cyclic_mul_naive);
+            check_ntt_backend(&reference, cyclic_mul_naive);
+
+            // agrees with reference backend for same \omega, that is outputs a(\omega^{brv(i)})
+            let a = random_vec_in_fq(n as usize, q);
+            let mut a_native = a.clone();
+            let mut a_reference = a.clone();
+            native.forward(&mut a_native);
+            reference.forward(&mut a_reference);
+            assert_eq!(a_native, a_reference);
+        }
+
+        // a(1) = \sum a_i is the first NTT domain value
+        let ntt_backend = NativeNTTBackend::builder(Q_60_BITS, N)
+            .mode(NttMode::Cyclic)
+            .build();
+        let mut a = vec![1u64; N as usize];
+        ntt_backend.forward(&mut a);
+        assert_eq!(a[0], N);
+        assert!(a[1..].iter().all(|a0| *a0 == 0));
+
+        // seeded \omega gives reproducible NTT tables
+        let a = random_vec_in_fq(N as usize, Q_60_BITS);
+        let ntt_seeded = |seed| {
+            let mut a = a.clone();
+            NativeNTTBackend::builder(Q_60_BITS, N)
+                .roots(RootSelection::WithRng(&mut StdRng::seed_from_u64(seed)))
+                .mode(NttMode::Cyclic)
+                .build()
+                .forward(&mut a);
+            a
+        };
+        assert_eq!(ntt_seeded(42), ntt_seeded(42));
+        assert_ne!(ntt_seeded(42), ntt_seeded(43));
+    }
+
+    #[test]
+    fn native_ntt_backend_inverse_scales_in_last_stage() {
+        for (q, n) in [
+            (Q_60_BITS, 2),
+            (Q_60_BITS, 4),
+            (Q_60_BITS, N),
+            (GOLDILOCKS_PRIME, 2),
+            (GOLDILOCKS_PRIME, N),
+        ] {
+            let new_deterministic = |mode| {
+                NativeNTTBackend::builder(q, n)
+                    .roots(RootSelection::Deterministic)
+                    .mode(mode)
+                    .build()
+            };
+            for (native, reference) in [
+                (
+                    new_deterministic(NttMode::Negacyclic),
+                    ReferenceNttBackend::new_deterministic(q, n),
+                ),
+                (
+                    new_deterministic(NttMode::Cyclic),
+                    ReferenceNttBackend::new_cyclic(q, n, find_primitive_root_deterministic(q, n)),
+                ),
+            ] {
+                for _ in 0..K {
+                    let a = random_vec_in_fq(n as usize, q);
+                    let mut a_native = a.clone();
+                    let mut a_reference = a.clone();
+                    native.inverse(&mut a_native);
+                    reference.inverse(&mut a_reference);
+                    assert_eq!(a_native, a_reference);
+                }
+            }
+        }
+
+        // lazy inverse accepts inputs in [0, 2q) and leaves outputs in [0, 2q)
+        let ntt_backend = NativeNTTBackend::new(Q_60_BITS, N);
+        let mut lazy_outputs = 0;
+        for _ in 0..K {
+            let a = random_vec_in_fq(N as usize, Q_60_BITS * 2);
+            let mut a_lazy = a.clone();
+            let mut a_reduced = a.iter().map(|a0| a0 % Q_60_BITS).collect_vec();
+            ntt_backend.inverse_lazy(&mut a_lazy);
+            ntt_backend.inverse(&mut a_reduced);
+            assert!(a_lazy.iter().all(|a0| *a0 < Q_60_BITS * 2));
+            assert!(izip!(a_lazy.iter(), a_reduced.iter()).all(|(l, r)| l % Q_60_BITS == *r));
+            lazy_outputs += a_lazy.iter().filter(|a0| **a0 >= Q_60_BITS).count();
+        }
+        assert!(lazy_outputs > 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn native_ntt_backend_rejects_ring_degree_1() {
+        NativeNTTBackend::new(Q_60_BITS, 1);
+    }
+
+    #[test]
+    fn native_ntt_backend_radix4_is_bit_identical() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (q, log_n) in [
+            (Q_60_BITS, 1..12),
+            (Q_SOLINAS_59_BITS, 1..6),
+            (GOLDILOCKS_PRIME, 1..6),
+        ] {
+            for n in log_n.map(|log_n| 1u64 << log_n) {
+                let seed = rng.gen();
+                let new_seeded = |kernel| {
+                    NativeNTTBackend::builder(q, n)
+                        .roots(RootSelection::WithRng(&mut StdRng::seed_from_u64(seed)))
+                        .kernel(kernel)
+                        .build()
+                };
+                let new_cyclic = |kernel| {
+                    NativeNTTBackend::builder(q, n)
+                        .roots(RootSelection::Deterministic)
+                        .mode(NttMode::Cyclic)
+                        .kernel(kernel)
+                        .build()
+                };
+                for (radix2, radix4) in [
+                    (new_seeded(NttKernel::Radix2), new_seeded(NttKernel::Radix4)),
+                    (new_cyclic(NttKernel::Radix2), new_cyclic(NttKernel::Radix4)),
+                ] {
+                    assert_eq!(radix4.kernel(), NttKernel::Radix4);
+                    for _ in 0..8 {
+                        // lazy intermediate values are compared as well
+                        let a = random_vec_in_fq(n as usize, q);
+                        let (mut a2, mut a4) = (a.clone(), a.clone());
+                        radix2.forward_lazy(&mut a2);
+                        radix4.forward_lazy(&mut a4);
+                        assert_eq!(a2, a4);
+
+                        radix2.inverse_lazy(&mut a2);
+                        radix4.inverse_lazy(&mut a4);
+                        assert_eq!(a2, a4);
+
+                        radix2.inverse(&mut a2);
+                        radix4.inverse(&mut a4);
+                        assert_eq!(a2, a4);
+                    }
+                }
+            }
+        }
+
+        check_ntt_backend(
+            &NativeNTTBackend::builder(Q_60_BITS, N)
+                .kernel(NttKernel::Radix4)
+                .build(),
+            negacyclic_mul_naive,
+        );
+        check_ntt_backend(
+            &NativeNTTBackend::builder(Q_60_BITS, 1 << 5)
+                .mode(NttMode::Cyclic)
+                .kernel(NttKernel::Radix4)
+                .build(),
+            cyclic_mul_naive,
+        );
+    }
 }
@@ -0,0 +1,71 @@
+use std::fmt::{Debug, Display};
+
+mod u256;
+
+use num_traits::{
+    AsPrimitive, CheckedShl, CheckedShr, Num, NumAssign, PrimInt, WrappingAdd, WrappingMul,
+    WrappingShl, WrappingShr, WrappingSub,
+};
+
+pub use u256::U256;
+
+pub trait NumericConstants {
+    const BITS: u32;
+    const MAX: Self;
+}
+
+pub trait UnsignedInteger:
+    PrimInt
+    + Num
+    + NumAssign
+    + WrappingMul
+    + WrappingAdd
+    + WrappingSub
+    + WrappingShl
+    + CheckedShl
+    + WrappingShr
+    + CheckedShr
+    + PartialOrd
+    + Display
+    + NumericConstants
+    + Debug
+    + Copy
+    + Clone
+{
+}
+
+/// Unsigned integer that has an unsigned integer type of twice its width.
+///
+/// `Doubled` is used to hold intermediate products (for ex, `a * b` in barrett and montgomery
+/// multiplication) without overflow. It is u64 for u32, u128 for u64 and [U256] for u128.
+pub trait UnsignedIntegerDoubled:
+    UnsignedInteger + AsPrimitive<<Self as UnsignedIntegerDoubled>::Doubled> + 'static
+{
+    type Doubled: UnsignedInteger + AsPrimitive<Self> + 'static;
+}
+
+impl NumericConstants for u32 {
+    const BITS: u32 = u32::BITS;
+    const MAX: u32 = u32::MAX;
+}
+
+impl NumericConstants for u64 {
+    const BITS: u32 = u64::BITS;
+    const MAX: u64 = u64::MAX;
+}
+
+impl NumericConstants for u128 {
+    const BITS: u32 = u128::BITS;
+    const MAX: u128 = u128::MAX;
+}
+
+impl UnsignedInteger for u32 {}
+impl UnsignedInteger for u64 {}
+impl UnsignedInteger for u128 {}
+
+impl UnsignedIntegerDoubled for u32 {
+    type Doubled = u64;
+}
+impl UnsignedIntegerDoubled for u64 {
+    type Doubled = u128;
+}
@@ -0,0 +1,751 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{
+        Add, AddAssign, BitAnd, BitOr, BitXor, Div, DivAssign, Mul, MulAssign, Not, Rem, RemAssign,
+        Shl, Shr, Sub, SubAssign,
+    },
+};
+
+use num_traits::{
+    AsPrimitive, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedShl, CheckedShr, CheckedSub,
+    Num, NumCast, One, PrimInt, Saturating, ToPrimitive, WrappingAdd, WrappingMul, WrappingShl,
+    WrappingShr, WrappingSub, Zero,
+};
+
+use super::{NumericConstants, UnsignedInteger, UnsignedIntegerDoubled};
+
+/// 256 bit unsigned integer.
+///
+/// U256 is the doubled type of u128 and only exists to hold intermediate products of u128 scalars.
+/// Hence it only implements what is required by `UnsignedInteger` and is not optimised for anything
+/// other than multiplication.
+///
+/// Like native unsigned integers, arithmetic operators panic on overflow in debug builds and wrap
+/// around in release builds.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256 {
+    // Field order matters. Derived `Ord` compares `hi` first.
+    hi: u128,
+    lo: u128,
+}
+
+impl U256 {
+    pub const ZERO: U256 = U256 { hi: 0, lo: 0 };
+    pub const ONE: U256 = U256 { hi: 0, lo: 1 };
+    pub const MAX: U256 = U256 {
+        hi: u128::MAX,
+        lo: u128::MAX,
+    };
+
+    pub const fn from_parts(hi: u128, lo: u128) -> U256 {
+        U256 { hi, lo }
+    }
+
+    /// Higher 128 bits
+    pub const fn hi(&self) -> u128 {
+        self.hi
+    }
+
+    /// Lower 128 bits
+    pub const fn lo(&self) -> u128 {
+        self.lo
+    }
+
+    /// Returns full 256 bit product of a and b
+    pub fn widening_mul(a: u128, b: u128) -> U256 {
+        let (a_hi, a_lo) = (a >> 64, a as u64 as u128);
+        let (b_hi, b_lo) = (b >> 64, b as u64 as u128);
+
+        let lo_lo = a_lo * b_lo;
+        let hi_lo = a_hi * b_lo;
+        let lo_hi = a_lo * b_hi;
+        let hi_hi = a_hi * b_hi;
+
+        // sum of middle terms along with carry from lower 64 bits. Cannot overflow.
+        let mid = (lo_lo >> 64) + (hi_lo as u64 as u128) + (lo_hi as u64 as u128);
+
+        U256 {
+            hi: hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (mid >> 64),
+            lo: (mid << 64) | (lo_lo as u64 as u128),
+        }
+    }
+
+    fn overflowing_add(self, rhs: U256) -> (U256, bool) {
+        let (lo, carry) = self.lo.overflowing_add(rhs.lo);
+        let (hi, o0) = self.hi.overflowing_add(rhs.hi);
+        let (hi, o1) = hi.overflowing_add(carry as u128);
+        (U256 { hi, lo }, o0 | o1)
+    }
+
+    fn overflowing_sub(self, rhs: U256) -> (U256, bool) {
+        let (lo, borrow) = self.lo.overflowing_sub(rhs.lo);
+        let (hi, o0) = self.hi.overflowing_sub(rhs.hi);
+        let (hi, o1) = hi.overflowing_sub(borrow as u128);
+        (U256 { hi, lo }, o0 | o1)
+    }
+
+    fn overflowing_mul(self, rhs: U256) -> (U256, bool) {
+        if self.hi == 0 && rhs.hi == 0 {
+            return (U256::widening_mul(self.lo, rhs.lo), false);
+        }
+
+        // hi * hi term only contributes to bits >= 256
+        let lo_lo = U256::widening_mul(self.lo, rhs.lo);
+        let hi_lo = U256::widening_mul(self.hi, rhs.lo);
+        let lo_hi = U256::widening_mul(self.lo, rhs.hi);
+        let (hi, o0) = lo_lo.hi.overflowing_add(hi_lo.lo);
+        let (hi, o1) = hi.overflowing_add(lo_hi.lo);
+
+        let overflow = o0 || o1 || hi_lo.hi != 0 || lo_hi.hi != 0 || (self.hi != 0 && rhs.hi != 0);
+        (U256 { hi, lo: lo_lo.lo }, overflow)
+    }
+
+    /// Returns (self / rhs, self % rhs) via binary long division
+    fn div_rem(self, rhs: U256) -> (U256, U256) {
+        assert!(rhs != U256::ZERO, "attempt to divide by zero");
+
+        if self.hi == 0 && rhs.hi == 0 {
+            return (
+                U256::from_parts(0, self.lo / rhs.lo),
+                U256::from_parts(0, self.lo % rhs.lo),
+            );
+        }
+        if self < rhs {
+            return (U256::ZERO, self);
+        }
+
+        let mut quotient = U256::ZERO;
+        let mut remainder = U256::ZERO;
+        let bits = 256 - self.leading_zeros();
+        for i in (0..bits as usize).rev() {
+            remainder = remainder << 1usize;
+            remainder.lo |= (self >> i).lo & 1;
+            if remainder >= rhs {
+                remainder = remainder.overflowing_sub(rhs).0;
+                quotient.set_bit(i);
+            }
+        }
+        (quotient, remainder)
+    }
+
+    /// Returns (self / rhs, self % rhs) for u64 `rhs`
+    fn div_rem_u64(self, rhs: u64) -> (U256, u64) {
+        let rhs = rhs as u128;
+        let mut quotient = [0u64; 4];
+        let mut remainder = 0u128;
+        for (i, limb) in self.limbs().iter().enumerate().rev() {
+            let current = (remainder << 64) | (*limb as u128);
+            quotient[i] = (current / rhs) as u64;
+            remainder = current % rhs;
+        }
+        (U256::from_limbs(quotient), remainder as u64)
+    }
+
+    fn set_bit(&mut self, i: usize) {
+        if i < 128 {
+            self.lo |= 1 << i;
+        } else {
+            self.hi |= 1 << (i - 128);
+        }
+    }
+
+    /// Little endian u64 limbs
+    fn limbs(&self) -> [u64; 4] {
+        [
+            self.lo as u64,
+            (self.lo >> 64) as u64,
+            self.hi as u64,
+            (self.hi >> 64) as u64,
+        ]
+    }
+
+    fn from_limbs(limbs: [u64; 4]) -> U256 {
+        U256 {
+            hi: ((limbs[3] as u128) << 64) | limbs[2] as u128,
+            lo: ((limbs[1] as u128) << 64) | limbs[0] as u128,
+        }
+    }
+
+    fn wrapping_shl_internal(self, rhs: usize) -> U256 {
+        let rhs = rhs & 255;
+        if rhs == 0 {
+            self
+        } else if rhs < 128 {
+            U256 {
+                hi: (self.hi << rhs) | (self.lo >> (128 - rhs)),
+                lo: self.lo << rhs,
+            }
+        } else {
+            U256 {
+                hi: self.lo << (rhs - 128),
+                lo: 0,
+            }
+        }
+    }
+
+    fn wrapping_shr_internal(self, rhs: usize) -> U256 {
+        let rhs = rhs & 255;
+        if rhs == 0 {
+            self
+        } else if rhs < 128 {
+            U256 {
+                hi: self.hi >> rhs,
+                lo: (self.lo >> rhs) | (self.hi << (128 - rhs)),
+            }
+        } else {
+            U256 {
+                hi: 0,
+                lo: self.hi >> (rhs - 128),
+            }
+        }
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        U256 { hi: 0, lo: value }
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        U256 {
+            hi: 0,
+            lo: value as u128,
+        }
+    }
+}
+
+impl NumericConstants for U256 {
+    const BITS: u32 = 256;
+    const MAX: U256 = U256::MAX;
+}
+
+impl UnsignedInteger for U256 {}
+
+impl UnsignedIntegerDoubled for u128 {
+    type Doubled = U256;
+}
+
+impl AsPrimitive<U256> for u128 {
+    #[inline]
+    fn as_(self) -> U256 {
+        U256::from_parts(0, self)
+    }
+}
+
+impl AsPrimitive<u128> for U256 {
+    #[inline]
+    fn as_(self) -> u128 {
+        self.lo
+    }
+}
+
+impl Display for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.hi == 0 {
+            return Display::fmt(&self.lo, f);
+        }
+
+        // peel off 19 decimal digits at a time
+        const TEN_POW_19: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = vec![];
+        let mut v = *self;
+        while v != U256::ZERO {
+            let (q, r) = v.div_rem_u64(TEN_POW_19);
+            chunks.push(r);
+            v = q;
+        }
+
+        let mut s = chunks.pop().unwrap().to_string();
+        chunks
+            .iter()
+            .rev()
+            .for_each(|c| s.push_str(&format!("{c:019}")));
+        f.pad_integral(true, "", &s)
+    }
+}
+
+impl Debug for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Add for U256 {
+    type Output = U256;
+    fn add(self, rhs: Self) -> Self::Output {
+        let (v, o) = self.overflowing_add(rhs);
+        debug_assert!(!o, "attempt to add with overflow");
+        v
+    }
+}
+
+impl Sub for U256 {
+    type Output = U256;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (v, o) = self.overflowing_sub(rhs);
+        debug_assert!(!o, "attempt to subtract with overflow");
+        v
+    }
+}
+
+impl Mul for U256 {
+    type Output = U256;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (v, o) = self.overflowing_mul(rhs);
+        debug_assert!(!o, "attempt to multiply with overflow");
+        v
+    }
+}
+
+impl Div for U256 {
+    type Output = U256;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem for U256 {
+    type Output = U256;
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).1
+    }
+}
+
+impl AddAssign for U256 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for U256 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for U256 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for U256 {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl RemAssign for U256 {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl Not for U256 {
+    type Output = U256;
+    fn not(self) -> Self::Output {
+        U256 {
+            hi: !self.hi,
+            lo: !self.lo,
+        }
+    }
+}
+
+impl BitAnd for U256 {
+    type Output = U256;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        U256 {
+            hi: self.hi & rhs.hi,
+            lo: self.lo & rhs.lo,
+        }
+    }
+}
+
+impl BitOr for U256 {
+    type Output = U256;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        U256 {
+            hi: self.hi | rhs.hi,
+            lo: self.lo | rhs.lo,
+        }
+    }
+}
+
+impl BitXor for U256 {
+    type Output = U256;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        U256 {
+            hi: self.hi ^ rhs.hi,
+            lo: self.lo ^ rhs.lo,
+        }
+    }
+}
+
+impl Shl<usize> for U256 {
+    type Output = U256;
+    fn shl(self, rhs: usize) -> Self::Output {
+        debug_assert!(rhs < 256, "attempt to shift left with overflow");
+        self.wrapping_shl_internal(rhs)
+    }
+}
+
+impl Shr<usize> for U256 {
+    type Output = U256;
+    fn shr(self, rhs: usize) -> Self::Output {
+        debug_assert!(rhs < 256, "attempt to shift right with overflow");
+        self.wrapping_shr_internal(rhs)
+    }
+}
+
+impl Shl<u32> for U256 {
+    type Output = U256;
+    fn shl(self, rhs: u32) -> Self::Output {
+        self << rhs as usize
+    }
+}
+
+impl Shr<u32> for U256 {
+    type Output = U256;
+    fn shr(self, rhs: u32) -> Self::Output {
+        self >> rhs as usize
+    }
+}
+
+impl Zero for U256 {
+    fn zero() -> Self {
+        U256::ZERO
+    }
+    fn is_zero(&self) -> bool {
+        *self == U256::ZERO
+    }
+}
+
+impl One for U256 {
+    fn one() -> Self {
+        U256::ONE
+    }
+}
+
+impl Num for U256 {
+    type FromStrRadixErr = &'static str;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if str.is_empty() {
+            return Err("cannot parse integer from empty string");
+        }
+
+        let radix_u256 = U256::from_parts(0, radix as u128);
+        str.chars().try_fold(U256::ZERO, |acc, c| {
+            let digit = c.to_digit(radix).ok_or("invalid digit found in string")?;
+            acc.checked_mul(&radix_u256)
+                .and_then(|v| v.checked_add(&U256::from_parts(0, digit as u128)))
+                .ok_or("number too large to fit in target type")
+        })
+    }
+}
+
+impl Bounded for U256 {
+    fn min_value() -> Self {
+        U256::ZERO
+    }
+    fn max_value() -> Self {
+        U256::MAX
+    }
+}
+
+impl ToPrimitive for U256 {
+    fn to_i64(&self) -> Option<i64> {
+        self.to_u128().and_then(|v| v.to_i64())
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|v| v.to_u64())
+    }
+    fn to_i128(&self) -> Option<i128> {
+        self.to_u128().and_then(|v| v.to_i128())
+    }
+    fn to_u128(&self) -> Option<u128> {
+        if self.hi == 0 {
+            Some(self.lo)
+        } else {
+            None
+        }
+    }
+}
+
+impl NumCast for U256 {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        n.to_u128().map(|v| U256::from_parts(0, v))
+    }
+}
+
+impl CheckedAdd for U256 {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        match self.overflowing_add(*v) {
+            (_, true) => None,
+            (r, false) => Some(r),
+        }
+    }
+}
+
+impl CheckedSub for U256 {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        match self.overflowing_sub(*v) {
+            (_, true) => None,
+            (r, false) => Some(r),
+        }
+    }
+}
+
+impl CheckedMul for U256 {
+    fn checked_mul(&self, v: &Self) -> Option<Self> {
+        match self.overflowing_mul(*v) {
+            (_, true) => None,
+            (r, false) => Some(r),
+        }
+    }
+}
+
+impl CheckedDiv for U256 {
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        if v.is_zero() {
+            None
+        } else {
+            Some(*self / *v)
+        }
+    }
+}
+
+impl CheckedShl for U256 {
+    fn checked_shl(&self, rhs: u32) -> Option<Self> {
+        if rhs < 256 {
+            Some(*self << rhs)
+        } else {
+            None
+        }
+    }
+}
+
+impl CheckedShr for U256 {
+    fn checked_shr(&self, rhs: u32) -> Option<Self> {
+        if rhs < 256 {
+            Some(*self >> rhs)
+        } else {
+            None
+        }
+    }
+}
+
+impl WrappingAdd for U256 {
+    fn wrapping_add(&self, v: &Self) -> Self {
+        self.overflowing_add(*v).0
+    }
+}
+
+impl WrappingSub for U256 {
+    fn wrapping_sub(&self, v: &Self) -> Self {
+        self.overflowing_sub(*v).0
+    }
+}
+
+impl WrappingMul for U256 {
+    fn wrapping_mul(&self, v: &Self) -> Self {
+        self.overflowing_mul(*v).0
+    }
+}
+
+impl WrappingShl for U256 {
+    fn wrapping_shl(&self, rhs: u32) -> Self {
+        self.wrapping_shl_internal(rhs as usize)
+    }
+}
+
+impl WrappingShr for U256 {
+    fn wrapping_shr(&self, rhs: u32) -> Self {
+        self.wrapping_shr_internal(rhs as usize)
+    }
+}
+
+impl Saturating for U256 {
+    fn saturating_add(self, v: Self) -> Self {
+        self.checked_add(&v).unwrap_or(U256::MAX)
+    }
+    fn saturating_sub(self, v: Self) -> Self {
+        self.checked_sub(&v).unwrap_or(U256::ZERO)
+    }
+}
+
+impl PrimInt for U256 {
+    fn count_ones(self) -> u32 {
+        self.hi.count_ones() + self.lo.count_ones()
+    }
+
+    fn count_zeros(self) -> u32 {
+        self.hi.count_zeros() + self.lo.count_zeros()
+    }
+
+    fn leading_zeros(self) -> u32 {
+        if self.hi == 0 {
+            128 + self.lo.leading_zeros()
+        } else {
+            self.hi.leading_zeros()
+        }
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        if self.lo == 0 {
+            128 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    fn rotate_left(self, n: u32) -> Self {
+        let n = (n & 255) as usize;
+        if n == 0 {
+            return self;
+        }
+        self.wrapping_shl_internal(n) | self.wrapping_shr_internal(256 - n)
+    }
+
+    fn rotate_right(self, n: u32) -> Self {
+        let n = (n & 255) as usize;
+        if n == 0 {
+            return self;
+        }
+        self.wrapping_shr_internal(n) | self.wrapping_shl_internal(256 - n)
+    }
+
+    fn signed_shl(self, n: u32) -> Self {
+        self << n
+    }
+
+    fn signed_shr(self, n: u32) -> Self {
+        let n = n as usize;
+        debug_assert!(n < 256, "attempt to shift right with overflow");
+        if self.hi >> 127 == 0 || n == 0 {
+            self >> n
+        } else {
+            (self >> n) | !(U256::MAX >> n)
+        }
+    }
+
+    fn unsigned_shl(self, n: u32) -> Self {
+        self << n
+    }
+
+    fn unsigned_shr(self, n: u32) -> Self {
+        self >> n
+    }
+
+    fn swap_bytes(self) -> Self {
+        U256 {
+            hi: self.lo.swap_bytes(),
+            lo: self.hi.swap_bytes(),
+        }
+    }
+
+    fn from_be(x: Self) -> Self {
+        if cfg!(target_endian = "big") {
+            x
+        } else {
+            x.swap_bytes()
+        }
+    }
+
+    fn from_le(x: Self) -> Self {
+        if cfg!(target_endian = "little") {
+            x
+        } else {
+            x.swap_bytes()
+        }
+    }
+
+    fn to_be(self) -> Self {
+        U256::from_be(self)
+    }
+
+    fn to_le(self) -> Self {
+        U256::from_le(self)
+    }
+
+    fn pow(self, mut exp: u32) -> Self {
+        let mut base = self;
+        let mut acc = U256::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc *= base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base *= base;
+            }
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    const K: usize = 1000;
+
+    #[test]
+    fn u256_arithmetic_works() {
+        let mut rng = thread_rng();
+        for _ in 0..K {
+            let a = rng.gen::<u128>();
+            let b = rng.gen::<u128>() | 1;
+
+            // (a * b) / b = a and (a * b) % b = 0
+            let ab = U256::widening_mul(a, b);
+            assert_eq!(ab, U256::from_parts(0, a) * U256::from_parts(0, b));
+            assert_eq!(ab / U256::from_parts(0, b), U256::from_parts(0, a));
+            assert_eq!(ab % U256::from_parts(0, b), U256::ZERO);
+
+            // (a * b + c) / b = a and (a * b + c) % b = c for c < b
+            let c = rng.gen_range(0..b);
+            let abc = ab + U256::from_parts(0, c);
+            assert_eq!(abc / U256::from_parts(0, b), U256::from_parts(0, a));
+            assert_eq!(abc % U256::from_parts(0, b), U256::from_parts(0, c));
+            assert_eq!(abc - ab, U256::from_parts(0, c));
+
+            // u128 arithmetic is unchanged
+            let a_small = a >> 1;
+            let b_small = b >> 1;
+            assert_eq!(
+                U256::from_parts(0, a_small) + U256::from_parts(0, b_small),
+                U256::from_parts(0, a_small + b_small)
+            );
+            assert_eq!(
+                U256::from_parts(0, a) % U256::from_parts(0, b),
+                U256::from_parts(0, a % b)
+            );
+
+            // shifts
+            let s: usize = rng.gen_range(0..256);
+            let v = U256::from_parts(rng.gen(), rng.gen());
+            assert_eq!((v >> s) << s, v & (U256::MAX << s));
+            assert_eq!(v.rotate_left(s as u32).rotate_right(s as u32), v);
+        }
+    }
+
+    #[test]
+    fn u256_display_and_parse_works() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(U256::MAX.to_string(), max);
+        assert_eq!(U256::from_str_radix(max, 10).unwrap(), U256::MAX);
+        assert_eq!(
+            (U256::ONE << 128usize).to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(U256::from_parts(0, 1234u128).to_string(), "1234");
+        assert_eq!(
+            U256::from_str_radix("ff", 16).unwrap(),
+            U256::from_parts(0, 255u128)
+        );
+    }
+}
@@ -0,0 +1,387 @@
+/// Classical security level in bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SecurityLevel {
+    Bits128,
+    Bits192,
+    Bits256,
+}
+
+/// Lattice parameters over `Z_Q[X]/(X^n + 1)`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParameterSet {
+    pub name: &'static str,
+    /// Ring degree n
+    pub ring_size: u64,
+    /// Upper bound on log2(Q)
+    pub max_log_q: u32,
+    /// Primes q_i s.t. Q = \prod q_i
+    pub moduli: &'static [u64],
+    /// Order of primitive root of unity that exists modulo every q_i. It is 2n if q_i support
+    /// full negacyclic NTT, otherwise the NTT is incomplete (for ex, Kyber has only n^{th} roots).
+    pub root_order: u64,
+    /// Security level of HE standard presets. None for PQC presets, since their security
+    /// additionally depends on module rank.
+    pub security: Option<SecurityLevel>,
+}
+
+impl ParameterSet {
+    /// Returns preset with `name`
+    pub fn by_name(name: &str) -> Option<&'static ParameterSet> {
+        HE_STANDARD_TERNARY
+            .iter()
+            .chain([&KYBER, &DILITHIUM])
+            .find(|p| p.name == name)
+    }
+}
+
+/// Kyber (ML-KEM) ring. q = 1 \mod{n}, but q != 1 \mod{2n}.
+///
+/// - [Reference](https://csrc.nist.gov/pubs/fips/203/final)
+pub const KYBER: ParameterSet = ParameterSet {
+    name: "kyber",
+    ring_size: 256,
+    max_log_q: 12,
+    moduli: &[3329],
+    root_order: 256,
+    security: None,
+};
+
+/// Dilithium (ML-DSA) ring
+///
+/// - [Reference](https://csrc.nist.gov/pubs/fips/204/final)
+pub const DILITHIUM: ParameterSet = ParameterSet {
+    name: "dilithium",
+    ring_size: 256,
+    max_log_q: 23,
+    moduli: &[8_380_417],
+    root_order: 512,
+    security: None,
+};
+
+/// Homomorphic Encryption Standard presets for ternary secrets, n = 1024, ..., 32768
+///
+/// For ring degree n and classical security level the standard bounds log2 of ciphertext modulus
+/// Q. Moduli of each preset are NTT friendly primes q_i = 1 \mod{2n} of at most 60 bits, with bit
+/// sizes splitting `max_log_q` evenly. For each bit size k, q_i are the nearest unused primes below
+/// 2^k (see [generate_ntt_primes](super::prime::generate_ntt_primes)).
+///
+/// - [Reference](https://homomorphicencryption.org/standard/)
+pub const HE_STANDARD_TERNARY: [ParameterSet; 18] = [
+    ParameterSet {
+        name: "he-std-ternary-128-1024",
+        ring_size: 1024,
+        max_log_q: 27,
+        moduli: &[134_215_681],
+        root_order: 2048,
+        security: Some(SecurityLevel::Bits128),
+    },
+    ParameterSet {
+        name: "he-std-ternary-192-1024",
+        ring_size: 1024,
+        max_log_q: 19,
+        moduli: &[520_193],
+        root_order: 2048,
+        security: Some(SecurityLevel::Bits192),
+    },
+    ParameterSet {
+        name: "he-std-ternary-256-1024",
+        ring_size: 1024,
+        max_log_q: 14,
+        moduli: &[12_289],
+        root_order: 2048,
+        security: Some(SecurityLevel::Bits256),
+    },
+    ParameterSet {
+        name: "he-std-ternary-128-2048",
+        ring_size: 2048,
+        max_log_q: 54,
+        moduli: &[18_014_398_509_404_161],
+        root_order: 4096,
+        security: Some(SecurityLevel::Bits128),
+    },
+    ParameterSet {
+        name: "he-std-ternary-192-2048",
+        ring_size: 2048,
+        max_log_q: 37,
+        moduli: &[137_438_822_401],
+        root_order: 4096,
+        security: Some(SecurityLevel::Bits192),
+    },
+    ParameterSet {
+        name: "he-std-ternary-256-2048",
+        ring_size: 2048,
+        max_log_q: 29,
+        moduli: &[536_813_569],
+        root_order: 4096,
+        security: Some(SecurityLevel::Bits256),
+    },
+    ParameterSet {
+        name: "he-std-ternary-128-4096",
+        ring_size: 4096,
+        max_log_q: 109,
+        moduli: &[36_028_797_018_652_673, 18_014_398_509_309_953],
+        root_order: 8192,
+        security: Some(SecurityLevel::Bits128),
+    },
+    ParameterSet {
+        name: "he-std-ternary-192-4096",
+        ring_size: 4096,
+        max_log_q: 75,
+        moduli: &[274_877_816_833, 137_438_822_401],
+        root_order: 8192,
+        security: Some(SecurityLevel::Bits192),
+    },
+    ParameterSet {
+        name: "he-std-ternary-256-4096",
+        ring_size: 4096,
+        max_log_q: 58,
+        moduli: &[288_230_376_151_130_113],
+        root_order: 8192,
+        security: Some(SecurityLevel::Bits256),
+    },
+    ParameterSet {
+        name: "he-std-ternary-128-8192",
+        ring_size: 8192,
+        max_log_q: 218,
+        moduli: &[
+            36_028_797_018_652_673,
+            36_028_797_017_571_329,
+            18_014_398_508_400_641,
+            18_014_398_508_138_497,
+        ],
+        root_order: 16384,
+        security: Some(SecurityLevel::Bits128),
+    },
+    ParameterSet {
+        name: "he-std-ternary-192-8192",
+        ring_size: 8192,
+        max_log_q: 152,
+        moduli: &[
+            2_251_799_813_554_177,
+            2_251_799_813_472_257,
+            1_125_899_906_826_241,
+        ],
+        root_order: 16384,
+        security: Some(SecurityLevel::Bits192),
+    },
+    ParameterSet {
+        name: "he-std-ternary-256-8192",
+        ring_size: 8192,
+        max_log_q: 118,
+        moduli: &[576_460_752_303_210_497, 576_460_752_303_046_657],
+        root_order: 16384,
+        security: Some(SecurityLevel::Bits256),
+    },
+    ParameterSet {
+        name: "he-std-ternary-128-16384",
+        ring_size: 16384,
+        max_log_q: 438,
+        moduli: &[
+            36_028_797_017_456_641,
+            36_028_797_016_178_689,
+            36_028_797_014_704_129,
+            36_028_797_014_573_057,
+            36_028_797_014_376_449,
+            36_028_797_014_081_537,
+            18_014_398_508_400_641,
+            18_014_398_508_138_497,
+        ],
+        root_order: 32768,
+        security: Some(SecurityLevel::Bits128),
+    },
+    ParameterSet {
+        name: "he-std-ternary-192-16384",
+        ring_size: 16384,
+        max_log_q: 305,
+        moduli: &[
+            2_251_799_813_554_177,
+            2_251_799_811_391_489,
+            2_251_799_810_670_593,
+            2_251_799_810_605_057,
+            2_251_799_809_916_929,
+            1_125_899_904_679_937,
+        ],
+        root_order: 32768,
+        security: Some(SecurityLevel::Bits192),
+    },
+    ParameterSet {
+        name: "he-std-ternary-256-16384",
+        ring_size: 16384,
+        max_log_q: 237,
+        moduli: &[
+            1_152_921_504_606_748_673,
+            576_460_752_302_473_217,
+            576_460_752_302_080_001,
+            576_460_752_301_785_089,
+        ],
+        root_order: 32768,
+        security: Some(SecurityLevel::Bits256),
+    },
+    ParameterSet {
+        name: "he-std-ternary-128-32768",
+        ring_size: 32768,
+        max_log_q: 881,
+        moduli: &[
+            576_460_752_301_785_089,
+            576_460_752_301_391_873,
+            576_460_752_300_015_617,
+            576_460_752_298_835_969,
+            576_460_752_298_180_609,
+            576_460_752_293_134_337,
+            576_460_752_291_954_689,
+            576_460_752_290_775_041,
+            576_460_752_290_119_681,
+            576_460_752_289_923_073,
+            576_460_752_289_529_857,
+            288_230_376_147_582_977,
+            288_230_376_147_386_369,
+            288_230_376_147_320_833,
+            288_230_376_144_568_321,
+        ],
+        root_order: 65536,
+        security: Some(SecurityLevel::Bits128),
+    },
+    ParameterSet {
+        name: "he-std-ternary-192-32768",
+        ring_size: 32768,
+        max_log_q: 611,
+        moduli: &[
+            72_057_594_037_338_113,
+            72_057_594_036_879_361,
+            72_057_594_036_551_681,
+            72_057_594_035_306_497,
+            72_057_594_034_913_281,
+            72_057_594_033_012_737,
+            36_028_797_017_456_641,
+            36_028_797_014_704_129,
+            36_028_797_014_573_057,
+            36_028_797_014_376_449,
+            36_028_797_013_327_873,
+        ],
+        root_order: 65536,
+        security: Some(SecurityLevel::Bits192),
+    },
+    ParameterSet {
+        name: "he-std-ternary-256-32768",
+        ring_size: 32768,
+        max_log_q: 476,
+        moduli: &[
+            1_152_921_504_606_584_833,
+            1_152_921_504_598_720_513,
+            1_152_921_504_597_016_577,
+            1_152_921_504_595_968_001,
+            576_460_752_301_785_089,
+            576_460_752_301_391_873,
+            576_460_752_300_015_617,
+            576_460_752_298_835_969,
+        ],
+        root_order: 65536,
+        security: Some(SecurityLevel::Bits256),
+    },
+];
+
+/// Returns HE standard preset for ternary secrets with ring degree `n` and `security`
+pub fn he_standard_ternary(n: u64, security: SecurityLevel) -> Option<&'static ParameterSet> {
+    HE_STANDARD_TERNARY
+        .iter()
+        .find(|p| p.ring_size == n && p.security == Some(security))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_crypto::prime::{
+        find_primitive_root, generate_ntt_primes, is_prime, PrimeSearchDirection,
+    };
+    use crate::utils::mod_exponent;
+    use rand::thread_rng;
+
+    fn all_presets() -> impl Iterator<Item = &'static ParameterSet> {
+        HE_STANDARD_TERNARY.iter().chain([&KYBER, &DILITHIUM])
+    }
+
+    #[test]
+    fn presets_have_ntt_friendly_primes() {
+        let mut rng = thread_rng();
+        for p in all_presets() {
+            let mut bits = 0;
+            for (i, q) in p.moduli.iter().enumerate() {
+                assert!(is_prime(*q), "{}: {q} is not prime", p.name);
+                assert!(!p.moduli[..i].contains(q), "{}: {q} is repeated", p.name);
+
+                let root = find_primitive_root(*q, p.root_order, &mut rng)
+                    .unwrap_or_else(|| panic!("{}: no {}^th root mod {q}", p.name, p.root_order));
+                assert_eq!(mod_exponent(root, p.root_order, *q), 1);
+                assert_eq!(mod_exponent(root, p.root_order / 2, *q), q - 1);
+
+                bits += 64 - q.leading_zeros();
+            }
+            assert!(
+                bits <= p.max_log_q,
+                "{}: Q has more than {} bits",
+                p.name,
+                p.max_log_q
+            );
+        }
+
+        assert_eq!(KYBER.moduli[0] % (2 * KYBER.ring_size), 257);
+    }
+
+    #[test]
+    fn he_standard_presets_match_prime_generator() {
+        for p in HE_STANDARD_TERNARY.iter() {
+            assert_eq!(p.root_order, 2 * p.ring_size);
+            let mut used = vec![];
+            for q in p.moduli {
+                let k = 64 - q.leading_zeros();
+                let expected =
+                    generate_ntt_primes(k, p.ring_size, 1, PrimeSearchDirection::Down, &used)[0];
+                assert_eq!(*q, expected, "{}", p.name);
+                used.push(*q);
+            }
+            // bit sizes use the full budget
+            assert_eq!(
+                p.moduli.iter().map(|q| 64 - q.leading_zeros()).sum::<u32>(),
+                p.max_log_q
+            );
+        }
+    }
+
+    #[test]
+    fn presets_lookup_works() {
+        let p = he_standard_ternary(8192, SecurityLevel::Bits128).unwrap();
+        assert_eq!(p.max_log_q, 218);
+        assert_eq!(ParameterSet::by_name(p.name), Some(p));
+        assert_eq!(
+            he_standard_ternary(32768, SecurityLevel::Bits256)
+                .unwrap()
+                .max_log_q,
+            476
+        );
+        assert_eq!(he_standard_ternary(512, SecurityLevel::Bits128), None);
+        assert_eq!(ParameterSet::by_name("kyber"), Some(&KYBER));
+        assert_eq!(
+            ParameterSet::by_name("dilithium").unwrap().moduli,
+            &[8380417]
+        );
+
+        // max log q decreases with security and increases with n
+        for n in [1024, 2048, 4096, 8192, 16384, 32768] {
+            let bounds = [
+                SecurityLevel::Bits128,
+                SecurityLevel::Bits192,
+                SecurityLevel::Bits256,
+            ]
+            .map(|s| he_standard_ternary(n, s).unwrap().max_log_q);
+            assert!(bounds[0] > bounds[1] && bounds[1] > bounds[2]);
+            if n < 32768 {
+                assert!(
+                    he_standard_ternary(2 * n, SecurityLevel::Bits128)
+                        .unwrap()
+                        .max_log_q
+                        > bounds[0]
+                );
+            }
+        }
+    }
+}
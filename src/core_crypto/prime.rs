@@ -1,17 +1,222 @@
 use rand::{Rng, RngCore};
+use std::collections::HashSet;
 
-use crate::utils::mod_exponent;
+use crate::utils::{factorize, jacobi, mod_exponent, mul_mod_routine};
+
+use super::{
+    modulus::{BarrettBackend, ModulusBackendConfig, NativeModulusBackend},
+    num::{UnsignedInteger, U256},
+};
+
+/// Find smallest primitive n^{th} root of unity in field F_q, where n is a power of two.
+///
+/// Unlike [find_primitive_root] the output is canonical and does not depend on randomness.
+/// Let z be the smallest quadratic non-residue. Then \omega = z^{(q-1)/n} is a primitive n^{th}
+/// root of unity, since \omega^{n/2} = z^{(q-1)/2} = -1. All primitive n^{th} roots are \omega^k
+/// for odd k, and we return the smallest of them.
+pub fn find_primitive_root_deterministic(q: u64, n: u64) -> u64 {
+    assert!(n.is_power_of_two() && n > 1, "{n} is not power of two");
+
+    // n^th root of unity only exists if n|(q-1)
+    assert!(q % n == 1, "{n}^th root of unity in F_{q} does not exists");
+
+    let z = (2..q)
+        .find(|z| jacobi(*z, q) == -1)
+        .expect("F_q has no quadratic non-residue");
+    let omega = mod_exponent(z, (q - 1) / n, q);
+
+    // \omega^k for odd k
+    let mul_mod = mul_mod_routine(q);
+    let omega_square = mul_mod(omega, omega);
+    let mut omega_k = omega;
+    let mut smallest = omega;
+    for _ in 0..n / 2 {
+        smallest = smallest.min(omega_k);
+        omega_k = mul_mod(omega_k, omega_square);
+    }
+    smallest
+}
+
+/// First 13 primes. Using them as Miller-Rabin witnesses correctly classifies all n < 3.3 * 10^24.
+///
+/// - [Reference](https://oeis.org/A014233)
+const MILLER_RABIN_WITNESSES: [u64; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Upper bound on n for which `MILLER_RABIN_WITNESSES` are deterministic
+const MILLER_RABIN_DETERMINISTIC_BOUND: u128 = 3317044064679887385961981;
+
+/// Returns true if odd `n` is a strong probable prime to base `witness`, where `1 < witness < n`
+/// and `mul_mod` computes a * b (mod n) for a, b < n.
+fn is_strong_probable_prime<Scalar, F>(n: Scalar, witness: Scalar, mul_mod: &F) -> bool
+where
+    Scalar: UnsignedInteger,
+    F: Fn(Scalar, Scalar) -> Scalar,
+{
+    let n_minus_one = n - Scalar::one();
+
+    // n - 1 = d * 2^s
+    let s = n_minus_one.trailing_zeros();
+    let mut d = n_minus_one >> (s as usize);
+
+    // x = witness^d
+    let mut x = Scalar::one();
+    let mut base = witness;
+    while d > Scalar::zero() {
+        if d & Scalar::one() == Scalar::one() {
+            x = mul_mod(x, base);
+        }
+        base = mul_mod(base, base);
+        d = d >> 1usize;
+    }
+
+    if x == Scalar::one() || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = mul_mod(x, x);
+        if x == n_minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// Checks `n` against witnesses that are small primes. Returns Some(result) if that alone
+/// decides primality of `n`, that is if `n` is either small or has a small factor.
+fn check_small_primes(n: u128) -> Option<bool> {
+    if n < 2 {
+        return Some(false);
+    }
+    for p in MILLER_RABIN_WITNESSES {
+        if n == p as u128 {
+            return Some(true);
+        }
+        if n.is_multiple_of(p as u128) {
+            return Some(false);
+        }
+    }
+    None
+}
+
+/// Deterministic Miller-Rabin primality test for u64.
+///
+/// Uses barrett reduction of `NativeModulusBackend<u64>` for n of at most 60 bits and of
+/// `NativeModulusBackend<u128>` otherwise.
+pub fn is_prime(n: u64) -> bool {
+    if let Some(result) = check_small_primes(n as u128) {
+        return result;
+    }
+
+    if n >> 60 == 0 {
+        let modulus = NativeModulusBackend::<u64>::initialise(n);
+        let mul_mod = |a, b| modulus.mul_mod_fast(a, b);
+        MILLER_RABIN_WITNESSES
+            .iter()
+            .all(|w| is_strong_probable_prime(n, *w, &mul_mod))
+    } else {
+        is_probable_prime_u128(n as u128, &[])
+    }
+}
+
+/// Miller-Rabin primality test for u128.
+///
+/// Deterministic for n < 3.3 * 10^24 (~81 bits). For larger n additionally tests against `rounds`
+/// random witnesses, thus a composite n is declared prime with probability at most 4^{-rounds}.
+///
+/// Uses barrett reduction of `NativeModulusBackend<u128>` for n of at most 124 bits, and falls
+/// back to reduction of `U256` products otherwise.
+pub fn is_probable_prime<R: RngCore>(n: u128, rounds: usize, rng: &mut R) -> bool {
+    if n <= u64::MAX as u128 {
+        return is_prime(n as u64);
+    }
+    if let Some(result) = check_small_primes(n) {
+        return result;
+    }
+
+    let random_witnesses = if n < MILLER_RABIN_DETERMINISTIC_BOUND {
+        vec![]
+    } else {
+        (0..rounds).map(|_| rng.gen_range(2..n - 1)).collect()
+    };
+    is_probable_prime_u128(n, &random_witnesses)
+}
+
+/// Miller-Rabin test for odd n > 41 against `MILLER_RABIN_WITNESSES` and `random_witnesses`
+fn is_probable_prime_u128(n: u128, random_witnesses: &[u128]) -> bool {
+    let witnesses = MILLER_RABIN_WITNESSES
+        .iter()
+        .map(|w| *w as u128)
+        .chain(random_witnesses.iter().copied());
+
+    if n >> 124 == 0 {
+        let modulus = NativeModulusBackend::<u128>::initialise(n);
+        let mul_mod = |a, b| modulus.mul_mod_fast(a, b);
+        witnesses
+            .into_iter()
+            .all(|w| is_strong_probable_prime(n, w, &mul_mod))
+    } else {
+        let n_u256 = U256::from(n);
+        let mul_mod = |a, b| (U256::widening_mul(a, b) % n_u256).lo();
+        witnesses
+            .into_iter()
+            .all(|w| is_strong_probable_prime(n, w, &mul_mod))
+    }
+}
+
+/// Returns true if \omega is a primitive m^{th} root of unity in F_q, given prime factorization of
+/// m. That is \omega^m = 1 and \omega^{m/p} != 1 for every prime p | m.
+fn is_primitive_root_with_factors(omega: u64, m: u64, m_factors: &[(u64, u32)], q: u64) -> bool {
+    mod_exponent(omega, m, q) == 1
+        && m_factors
+            .iter()
+            .all(|(p, _)| mod_exponent(omega, m / p, q) != 1)
+}
+
+/// Returns true if \omega is a primitive m^{th} root of unity in F_q
+pub fn is_primitive_root(omega: u64, m: u64, q: u64) -> bool {
+    assert!(m > 0, "Order must be positive");
+    is_primitive_root_with_factors(omega % q, m, &factorize(m), q)
+}
+
+/// Find smallest generator of multiplicative group F_q^*, where q is prime.
+///
+/// g is a generator iff g^{(q-1)/p} != 1 for every prime p | q - 1.
+pub fn find_generator(q: u64) -> u64 {
+    assert!(is_prime(q), "{q} is not prime");
+
+    let factors = factorize(q - 1);
+    (1..q)
+        .find(|g| is_primitive_root_with_factors(*g, q - 1, &factors, q))
+        .expect("F_q^* is cyclic")
+}
+
+/// Find primitive m^{th} root of unity in field F_q for arbitrary m, if one exists.
+///
+/// Returns g^{(q-1)/m}, where g is the smallest generator of F_q^* (see [find_generator]), thus
+/// the output is canonical. Returns None if m does not divide q - 1.
+pub fn find_primitive_root_of_order(q: u64, m: u64) -> Option<u64> {
+    assert!(m > 0, "Order must be positive");
+
+    if !(q - 1).is_multiple_of(m) {
+        return None;
+    }
+    Some(mod_exponent(find_generator(q), (q - 1) / m, q))
+}
 
 /// Find n^{th} root of unity in field F_q, if one exists
 ///
 /// Note: n^{th} root of unity exists if and only if $q = 1 \mod{n}$
 pub(crate) fn find_primitive_root<R: RngCore>(q: u64, n: u64, rng: &mut R) -> Option<u64> {
-    assert!(n.is_power_of_two(), "{n} is not power of two");
+    assert!(n > 0, "Order must be positive");
 
     // n^th root of unity only exists if n|(q-1)
-    assert!(q % n == 1, "{n}^th root of unity in F_{q} does not exists");
+    assert!(
+        (q - 1).is_multiple_of(n),
+        "{n}^th root of unity in F_{q} does not exists"
+    );
 
     let t = (q - 1) / n;
+    let n_factors = factorize(n);
 
     for _ in 0..100 {
         let mut omega = rng.gen::<u64>() % q;
@@ -19,10 +224,9 @@ pub(crate) fn find_primitive_root<R: RngCore>(q: u64, n: u64, rng: &mut R) -> Op
         // \omega = \omega^t. \omega is now n^th root of unity
         omega = mod_exponent(omega, t, q);
 
-        // We restrict n to be power of 2. Thus checking whether \omega is primitive n^th root of unity is a simple check: \omega^{n/2} != 1
-        if mod_exponent(omega, n >> 1, q) == 1 {
-            continue;
-        } else {
+        // \omega is primitive n^th root of unity if \omega^{n/p} != 1 for all primes p | n. For n
+        // power of two this is simply \omega^{n/2} != 1
+        if is_primitive_root_with_factors(omega, n, &n_factors, q) {
             return Some(omega);
         }
     }
@@ -30,13 +234,268 @@ pub(crate) fn find_primitive_root<R: RngCore>(q: u64, n: u64, rng: &mut R) -> Op
     None
 }
 
+/// Direction in which [NttPrimeGenerator] searches for primes starting at 2^k
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrimeSearchDirection {
+    /// Primes in (2^{k-1}, 2^k) in decreasing order, that is k bit primes
+    Down,
+    /// Primes in (2^k, 2^{k+1}) in increasing order, that is k+1 bit primes
+    Up,
+}
+
+/// Iterator over NTT friendly primes q = 1 \mod{2n} nearest to 2^k.
+///
+/// Since n^{th} primitive root of unity exists iff q = 1 \mod{n}, F_q has the roots of unity
+/// required by negacyclic NTT of size n. Iteration ends once the search leaves the bit length.
+///
+/// Note that [PrimeSearchDirection::Up] yields k+1 bit primes. `NativeNTTBackend` supports primes
+/// of at most 60 bits, and primes of 61-64 bits only if they are of special form (see
+/// `SpecialForm::detect`), otherwise it panics. Primes found searching down from 2^k, for
+/// 60 < k < 64, are of special form as long as they are within 2^{k/2} of 2^k. Primes found
+/// searching up never are, and the only 64 bit prime of special form is Goldilocks. Thus for
+/// `NativeNTTBackend` use `Up` only with k < 60 and `Down` only with k < 64.
+pub struct NttPrimeGenerator {
+    /// 2n
+    step: u128,
+    direction: PrimeSearchDirection,
+    /// Next candidate
+    candidate: u128,
+    /// Search range [lower, upper)
+    lower: u128,
+    upper: u128,
+    excluded: HashSet<u64>,
+}
+
+impl NttPrimeGenerator {
+    /// Returns generator of primes q = 1 \mod{2n} starting at 2^k and searching in `direction`.
+    /// Primes are k bit for [PrimeSearchDirection::Down] and k+1 bit for
+    /// [PrimeSearchDirection::Up].
+    pub fn new(k: u32, n: u64, direction: PrimeSearchDirection) -> NttPrimeGenerator {
+        assert!(n.is_power_of_two(), "{n} is not power of two");
+        assert!(
+            (2 * n as u128) < (1u128 << (k - 1)),
+            "2n = {} is too large for {k} bit primes",
+            2 * n
+        );
+
+        let step = 2 * n as u128;
+        let (candidate, lower, upper) = match direction {
+            PrimeSearchDirection::Down => {
+                assert!(k <= 64, "Primes must fit in u64");
+                // 2^k = 0 \mod{2n}
+                ((1u128 << k) - step + 1, 1u128 << (k - 1), 1u128 << k)
+            }
+            PrimeSearchDirection::Up => {
+                assert!(k < 64, "Primes must fit in u64");
+                ((1u128 << k) + 1, 1u128 << k, 1u128 << (k + 1))
+            }
+        };
+
+        NttPrimeGenerator {
+            step,
+            direction,
+            candidate,
+            lower,
+            upper,
+            excluded: HashSet::new(),
+        }
+    }
+
+    /// Skips `primes`, for ex ones already used in another RNS chain
+    pub fn exclude(mut self, primes: &[u64]) -> NttPrimeGenerator {
+        self.excluded.extend(primes.iter().copied());
+        self
+    }
+}
+
+impl Iterator for NttPrimeGenerator {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.candidate >= self.lower && self.candidate < self.upper {
+            let q = self.candidate as u64;
+            match self.direction {
+                PrimeSearchDirection::Down => self.candidate -= self.step,
+                PrimeSearchDirection::Up => self.candidate += self.step,
+            }
+
+            if !self.excluded.contains(&q) && is_prime(q) {
+                return Some(q);
+            }
+        }
+        None
+    }
+}
+
+/// Returns `count` distinct primes q = 1 \mod{2n} nearest to 2^k in `direction`, skipping primes
+/// in `exclude`.
+///
+/// Panics if there are fewer than `count` such primes.
+pub fn generate_ntt_primes(
+    k: u32,
+    n: u64,
+    count: usize,
+    direction: PrimeSearchDirection,
+    exclude: &[u64],
+) -> Vec<u64> {
+    let primes = NttPrimeGenerator::new(k, n, direction)
+        .exclude(exclude)
+        .take(count)
+        .collect::<Vec<_>>();
+    assert!(
+        primes.len() == count,
+        "Only {} primes q = 1 mod {} found near 2^{k}",
+        primes.len(),
+        2 * n
+    );
+    primes
+}
+
+/// CKKS modulus chain Q = q_0 * q_1 * ... * q_L and special modulus P = p_0 * ... * p_{k-1}
+#[derive(Clone, Debug)]
+pub struct CkksModulusChain {
+    /// q_0, which holds the message after the last rescale
+    pub base_prime: u64,
+    /// q_1, ..., q_L, where rescaling at level l divides by q_l
+    pub rescaling_primes: Vec<u64>,
+    /// Special primes for key switching
+    pub special_primes: Vec<u64>,
+    /// Relative scale error \Delta_l / 2^{scale_bits} - 1 at level l = 0, ..., L, where
+    /// \Delta_L = 2^{scale_bits} and \Delta_{l-1} = \Delta_l^2 / q_l
+    pub scale_errors: Vec<f64>,
+}
+
+impl CkksModulusChain {
+    /// Returns q_0, q_1, ..., q_L
+    pub fn ciphertext_primes(&self) -> Vec<u64> {
+        std::iter::once(self.base_prime)
+            .chain(self.rescaling_primes.iter().copied())
+            .collect()
+    }
+
+    pub fn levels(&self) -> usize {
+        self.rescaling_primes.len()
+    }
+}
+
+/// Builds [CkksModulusChain] with rescaling primes as close as possible to 2^{scale_bits}.
+///
+/// Multiplication squares the scale and rescaling at level l divides it by q_l. Thus
+/// rescaling primes are picked greedily, at each level choosing the nearest unused prime
+/// above or below 2^{scale_bits} that brings \Delta_{l-1} = \Delta_l^2 / q_l closest to
+/// 2^{scale_bits}. This alternates primes around 2^{scale_bits} and keeps scale drift bounded,
+/// whereas using only primes below (or above) 2^{scale_bits} makes the drift grow with every
+/// level.
+pub struct CkksChainBuilder {
+    n: u64,
+    scale_bits: u32,
+    levels: usize,
+    base_bits: u32,
+    special_count: usize,
+    special_bits: u32,
+}
+
+impl CkksChainBuilder {
+    /// Chain with `levels` rescaling primes near 2^{scale_bits} for ring degree `n`.
+    ///
+    /// By default base prime has `scale_bits + 20` bits (at most 60) and there is a single
+    /// special prime of 60 bits.
+    pub fn new(n: u64, scale_bits: u32, levels: usize) -> CkksChainBuilder {
+        CkksChainBuilder {
+            n,
+            scale_bits,
+            levels,
+            base_bits: (scale_bits + 20).min(60),
+            special_count: 1,
+            special_bits: 60,
+        }
+    }
+
+    pub fn base_bits(mut self, bits: u32) -> CkksChainBuilder {
+        self.base_bits = bits;
+        self
+    }
+
+    pub fn special_primes(mut self, count: usize, bits: u32) -> CkksChainBuilder {
+        self.special_count = count;
+        self.special_bits = bits;
+        self
+    }
+
+    /// Panics if there are not enough NTT friendly primes of requested sizes
+    pub fn build(&self) -> CkksModulusChain {
+        let mut below =
+            NttPrimeGenerator::new(self.scale_bits, self.n, PrimeSearchDirection::Down).peekable();
+        let mut above =
+            NttPrimeGenerator::new(self.scale_bits, self.n, PrimeSearchDirection::Up).peekable();
+
+        let scale = (self.scale_bits as f64).exp2();
+
+        // \Delta_l / 2^{scale_bits}
+        let mut scale_ratio = 1.0f64;
+        let mut scale_errors = vec![0.0; self.levels + 1];
+        // picked from q_L down to q_1
+        let mut rescaling_primes = Vec::with_capacity(self.levels);
+        for level in (1..=self.levels).rev() {
+            let ratio_after = |q: &u64| scale_ratio * scale_ratio / (*q as f64 / scale);
+            let q = match (below.peek(), above.peek()) {
+                (Some(q_below), Some(q_above)) => {
+                    if (ratio_after(q_below) - 1.0).abs() <= (ratio_after(q_above) - 1.0).abs() {
+                        below.next()
+                    } else {
+                        above.next()
+                    }
+                }
+                (Some(_), None) => below.next(),
+                (None, Some(_)) => above.next(),
+                (None, None) => None,
+            }
+            .unwrap_or_else(|| {
+                panic!(
+                    "Not enough primes near 2^{} for {} levels",
+                    self.scale_bits, self.levels
+                )
+            });
+
+            scale_ratio = ratio_after(&q);
+            scale_errors[level - 1] = scale_ratio - 1.0;
+            rescaling_primes.push(q);
+        }
+        rescaling_primes.reverse();
+
+        let base_prime = generate_ntt_primes(
+            self.base_bits,
+            self.n,
+            1,
+            PrimeSearchDirection::Down,
+            &rescaling_primes,
+        )[0];
+
+        let mut used = rescaling_primes.clone();
+        used.push(base_prime);
+        let special_primes = generate_ntt_primes(
+            self.special_bits,
+            self.n,
+            self.special_count,
+            PrimeSearchDirection::Down,
+            &used,
+        );
+
+        CkksModulusChain {
+            base_prime,
+            rescaling_primes,
+            special_primes,
+            scale_errors,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use rand::thread_rng;
 
-    use crate::core_crypto::modulus::{BarrettBackend, ModulusBackendConfig, NativeModulusBackend};
-
     use super::*;
+    use crate::core_crypto::ntt::NativeNTTBackend;
 
     const Q_60_BITS: u64 = 1152921504606748673;
     const N: u64 = 1 << 15;
@@ -50,7 +509,7 @@ mod test {
         // TODO (Jay): Hardcode tests here, probably with some python script
         let modulus = NativeModulusBackend::initialise(Q_60_BITS);
         let mut root_n = 1;
-        for i in 0..N {
+        for _ in 0..N {
             root_n = modulus.mul_mod_fast(root_n, root);
         }
 
@@ -59,4 +518,285 @@ mod test {
             "Incorrect {N}^th root of unity: {root}^{N} != 1"
         );
     }
+
+    #[test]
+    fn is_prime_works() {
+        // sieve of eratosthenes
+        const N: usize = 100000;
+        let mut sieve = vec![true; N];
+        sieve[0] = false;
+        sieve[1] = false;
+        for i in 2..N {
+            if sieve[i] {
+                (i * i..N).step_by(i).for_each(|j| sieve[j] = false);
+            }
+        }
+        for (n, expected) in sieve.iter().enumerate() {
+            assert_eq!(is_prime(n as u64), *expected, "{n}");
+        }
+
+        for p in [
+            Q_60_BITS,
+            1125899906826241,
+            (1 << 61) - 1,
+            0xffff_ffff_0000_0001,
+            u64::MAX - 58,
+        ] {
+            assert!(is_prime(p), "{p} is prime");
+        }
+
+        for n in [
+            // carmichael numbers
+            561,
+            41041,
+            825265,
+            // strong pseudoprime to bases 2, 3, 5 and 7
+            3215031751,
+            // strong pseudoprime to all prime bases upto 37
+            3825123056546413051,
+            // product of two 32 bit primes
+            4294967291 * 4294967279,
+            u64::MAX,
+            Q_60_BITS * 3,
+        ] {
+            assert!(!is_prime(n), "{n} is composite");
+        }
+    }
+
+    #[test]
+    fn is_probable_prime_works() {
+        let mut rng = thread_rng();
+        for p in [
+            // 100 bits
+            1267650600228229401496703205361u128,
+            // 124 bits
+            21267647932558653966460912964485513157,
+            (1 << 127) - 1,
+            u128::MAX - 158,
+            u64::MAX as u128 - 58,
+        ] {
+            assert!(is_probable_prime(p, 20, &mut rng), "{p} is prime");
+        }
+
+        for n in [
+            // strong pseudoprime to all prime bases upto 37
+            318665857834031151167461u128,
+            ((1 << 61) - 1) * (u64::MAX as u128 - 58),
+            Q_60_BITS as u128 * 1125899906826241,
+            u128::MAX,
+            (1 << 127) + 1,
+        ] {
+            assert!(!is_probable_prime(n, 20, &mut rng), "{n} is composite");
+        }
+    }
+
+    #[test]
+    fn ntt_prime_generator_works() {
+        let primes = generate_ntt_primes(60, 1 << 14, 3, PrimeSearchDirection::Down, &[]);
+        assert_eq!(
+            primes,
+            vec![Q_60_BITS, 1152921504606683137, 1152921504606584833]
+        );
+
+        let primes = generate_ntt_primes(50, 1 << 12, 3, PrimeSearchDirection::Up, &[]);
+        assert_eq!(
+            primes,
+            vec![1125899906949121, 1125899906990081, 1125899907063809]
+        );
+
+        // excluded primes are skipped
+        let primes = generate_ntt_primes(
+            60,
+            1 << 14,
+            2,
+            PrimeSearchDirection::Down,
+            &[1152921504606683137],
+        );
+        assert_eq!(primes, vec![Q_60_BITS, 1152921504606584833]);
+
+        // all primes in range are found
+        let primes =
+            NttPrimeGenerator::new(10, 1 << 5, PrimeSearchDirection::Down).collect::<Vec<_>>();
+        assert_eq!(primes, vec![769, 641, 577]);
+        let primes =
+            NttPrimeGenerator::new(9, 1 << 5, PrimeSearchDirection::Up).collect::<Vec<_>>();
+        assert_eq!(primes, vec![577, 641, 769]);
+
+        // many primes are distinct, ntt friendly and of requested bit length
+        let n = 1 << 16;
+        for (k, direction, bits) in [
+            (55, PrimeSearchDirection::Down, 55),
+            (55, PrimeSearchDirection::Up, 56),
+            (64, PrimeSearchDirection::Down, 64),
+        ] {
+            let primes = generate_ntt_primes(k, n, 20, direction, &[]);
+            assert_eq!(primes.iter().collect::<HashSet<_>>().len(), 20);
+            for q in primes {
+                assert!(is_prime(q));
+                assert_eq!(q % (2 * n), 1);
+                assert_eq!(64 - q.leading_zeros(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn ntt_prime_generator_primes_work_with_native_ntt_backend() {
+        let n = 1 << 10;
+        // primes of more than 60 bits nearest to 2^k are of special form
+        for (k, direction) in [
+            (50, PrimeSearchDirection::Up),
+            (60, PrimeSearchDirection::Down),
+            (62, PrimeSearchDirection::Down),
+            (63, PrimeSearchDirection::Down),
+        ] {
+            for q in generate_ntt_primes(k, n, 2, direction, &[]) {
+                NativeNTTBackend::new(q, n);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn ntt_prime_generator_up_from_60_bits_is_unsupported_by_native_ntt_backend() {
+        let q = generate_ntt_primes(60, 1 << 10, 1, PrimeSearchDirection::Up, &[])[0];
+        assert_eq!(64 - q.leading_zeros(), 61);
+        NativeNTTBackend::new(q, 1 << 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ntt_prime_generator_panics_if_exhausted() {
+        generate_ntt_primes(10, 1 << 5, 4, PrimeSearchDirection::Down, &[]);
+    }
+
+    #[test]
+    fn ckks_chain_builder_works() {
+        let n = 1 << 12;
+        let scale_bits = 40;
+        let levels = 12;
+        let chain = CkksChainBuilder::new(n, scale_bits, levels)
+            .special_primes(2, 60)
+            .build();
+
+        assert_eq!(chain.levels(), levels);
+        assert_eq!(chain.scale_errors.len(), levels + 1);
+        assert_eq!(chain.scale_errors[levels], 0.0);
+        assert_eq!(64 - chain.base_prime.leading_zeros(), 60);
+        assert_eq!(chain.special_primes.len(), 2);
+
+        let mut all_primes = chain.ciphertext_primes();
+        all_primes.extend(chain.special_primes.iter());
+        assert_eq!(all_primes.iter().collect::<HashSet<_>>().len(), levels + 3);
+        for q in all_primes {
+            assert!(is_prime(q));
+            assert_eq!(q % (2 * n), 1);
+        }
+
+        // rescaling primes lie on both sides of 2^{scale_bits}
+        let scale = 1u64 << scale_bits;
+        assert!(chain.rescaling_primes.iter().any(|q| *q < scale));
+        assert!(chain.rescaling_primes.iter().any(|q| *q > scale));
+
+        // reported scale errors are consistent with primes
+        let mut scale_ratio = 1.0f64;
+        for level in (1..=levels).rev() {
+            let q_ratio = chain.rescaling_primes[level - 1] as f64 / scale as f64;
+            scale_ratio = scale_ratio * scale_ratio / q_ratio;
+            assert!(((scale_ratio - 1.0) - chain.scale_errors[level - 1]).abs() < 1e-12);
+        }
+
+        // drift is smaller than when using only primes below 2^{scale_bits}
+        let max_error = chain
+            .scale_errors
+            .iter()
+            .fold(0.0f64, |acc, e| acc.max(e.abs()));
+        let mut scale_ratio = 1.0f64;
+        let mut max_error_below = 0.0f64;
+        for q in generate_ntt_primes(scale_bits, n, levels, PrimeSearchDirection::Down, &[]) {
+            scale_ratio = scale_ratio * scale_ratio / (q as f64 / scale as f64);
+            max_error_below = max_error_below.max((scale_ratio - 1.0).abs());
+        }
+        assert!(
+            max_error < max_error_below,
+            "{max_error} >= {max_error_below}"
+        );
+        assert!(max_error < 1e-6, "{max_error}");
+    }
+
+    #[test]
+    fn find_primitive_root_deterministic_works() {
+        // smallest primitive n^th root by brute force
+        for (q, n) in [(97u64, 32u64), (7681, 512), (12289, 2048), (3329, 256)] {
+            let mut n_i = 2;
+            while n_i <= n {
+                let expected = (2..q)
+                    .find(|x| mod_exponent(*x, n_i, q) == 1 && mod_exponent(*x, n_i / 2, q) != 1)
+                    .unwrap();
+                assert_eq!(find_primitive_root_deterministic(q, n_i), expected);
+                n_i <<= 1;
+            }
+        }
+
+        for q in [Q_60_BITS, 0xffff_ffff_0000_0001] {
+            let root = find_primitive_root_deterministic(q, N);
+            assert_eq!(mod_exponent(root, N, q), 1);
+            assert_eq!(mod_exponent(root, N / 2, q), q - 1);
+            assert_eq!(root, find_primitive_root_deterministic(q, N));
+        }
+    }
+
+    #[test]
+    fn find_generator_works() {
+        for q in (2..2000u64).filter(|q| is_prime(*q)) {
+            let g = find_generator(q);
+            // smallest element of order q - 1
+            let expected = (1..q)
+                .find(|g| (1..q - 1).all(|k| mod_exponent(*g, k, q) != 1))
+                .unwrap();
+            assert_eq!(g, expected, "generator of F_{q}");
+        }
+        assert_eq!(find_generator(0xffff_ffff_0000_0001), 7);
+    }
+
+    #[test]
+    fn find_primitive_root_of_order_works() {
+        let order_naive =
+            |omega: u64, q: u64| (1..q).find(|k| mod_exponent(omega, *k, q) == 1).unwrap();
+
+        // 7680 = 2^9 * 3 * 5
+        let q = 7681;
+        let mut rng = thread_rng();
+        for m in 1..q {
+            let omega = find_primitive_root_of_order(q, m);
+            if (q - 1) % m != 0 {
+                assert!(omega.is_none());
+                continue;
+            }
+            let omega = omega.unwrap();
+            assert_eq!(order_naive(omega, q), m);
+            assert!(is_primitive_root(omega, m, q));
+
+            let omega = find_primitive_root(q, m, &mut rng).unwrap();
+            assert_eq!(order_naive(omega, q), m);
+        }
+
+        // non power of two orders in large fields
+        for (q, m) in [
+            (Q_60_BITS, N * 2087),
+            (0xffff_ffff_0000_0001, 3 * 5 * 17 * 257 * 65537),
+        ] {
+            for omega in [
+                find_primitive_root_of_order(q, m).unwrap(),
+                find_primitive_root(q, m, &mut rng).unwrap(),
+            ] {
+                assert!(is_primitive_root(omega, m, q));
+                assert!(!is_primitive_root(omega, m * 2, q));
+                // \omega^p has order m/p for p | m
+                for (p, _) in factorize(m) {
+                    assert!(!is_primitive_root(mod_exponent(omega, p, q), m, q));
+                    assert!(is_primitive_root(mod_exponent(omega, p, q), m / p, q));
+                }
+            }
+        }
+    }
 }
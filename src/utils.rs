@@ -1,5 +1,10 @@
-use crate::core_crypto::modulus::{BarrettBackend, ModulusBackendConfig, NativeModulusBackend};
-use std::mem;
+use crate::core_crypto::{
+    modulus::{BarrettBackend, ModulusBackendConfig, NativeModulusBackend},
+    num::U256,
+    prime::is_prime,
+};
+use num_traits::{WrappingMul, WrappingSub};
+use std::{collections::HashMap, mem};
 
 pub trait FastModularInverse {
     /// Calculates modular inverse of `a` in `Self`
@@ -32,8 +37,7 @@ impl FastModularInverse for u64 {
         let x5 = x4.wrapping_mul(y.wrapping_add(1));
         y = y.wrapping_mul(y);
 
-        let x6 = x5.wrapping_mul(y.wrapping_add(1));
-        x6
+        x5.wrapping_mul(y.wrapping_add(1))
     }
 }
 
@@ -57,8 +61,38 @@ impl FastModularInverse for u32 {
         let x4 = x3.wrapping_mul(y.wrapping_add(1));
         y = y.wrapping_mul(y);
 
-        let x5 = x4.wrapping_mul(y.wrapping_add(1));
-        x5
+        x4.wrapping_mul(y.wrapping_add(1))
+    }
+}
+
+impl FastModularInverse for u128 {
+    fn fast_inverse(a: Self) -> Self {
+        assert!(a & 1 == 1, "Modulus inverse of {a} does not exit");
+
+        // inverse (mod 2^64) lifted to inverse (mod 2^128) with a single newton iteration
+        let x = u64::fast_inverse(a as u64) as u128;
+        x.wrapping_mul(2u128.wrapping_sub(a.wrapping_mul(x)))
+    }
+}
+
+impl FastModularInverse for U256 {
+    fn fast_inverse(a: Self) -> Self {
+        assert!(a.lo() & 1 == 1, "Modulus inverse of {a} does not exit");
+
+        // inverse (mod 2^128) lifted to inverse (mod 2^256) with a single newton iteration
+        let x = U256::from(u128::fast_inverse(a.lo()));
+        x.wrapping_mul(&U256::from(2u128).wrapping_sub(&a.wrapping_mul(&x)))
+    }
+}
+
+/// Returns routine calculating a * b \mod{q} for a, b < q
+pub(crate) fn mul_mod_routine(q: u64) -> impl Fn(u64, u64) -> u64 {
+    // Barrett reduction supports moduli of at most 60 bits, and `NativeModulusBackend` requires
+    // odd moduli
+    let modulus = (q >> 60 == 0 && q & 1 == 1).then(|| NativeModulusBackend::initialise(q));
+    move |a: u64, b: u64| match &modulus {
+        Some(modulus) => modulus.mul_mod_fast(a, b),
+        None => ((a as u128 * b as u128) % q as u128) as u64,
     }
 }
 
@@ -68,15 +102,15 @@ pub fn mod_exponent(a: u64, mut n: u64, q: u64) -> u64 {
     let mut a_prod = a;
     let mut a_n = 1;
 
-    let modulus = NativeModulusBackend::initialise(q);
+    let mul_mod = mul_mod_routine(q);
 
     while n > 0 {
         if n & 1 == 1 {
-            a_n = modulus.mul_mod_fast(a_prod, a_n);
+            a_n = mul_mod(a_prod, a_n);
         }
-        a_prod = modulus.mul_mod_fast(a_prod, a_prod);
+        a_prod = mul_mod(a_prod, a_prod);
 
-        n = n >> 1u32;
+        n >>= 1u32;
     }
 
     a_n
@@ -84,16 +118,75 @@ pub fn mod_exponent(a: u64, mut n: u64, q: u64) -> u64 {
 
 /// Calculates modular inverse `a^{-1}` of `a` s.t. a * a^{-1} = 1 \mod{q}
 ///
-/// TODO (Jay): Add tests and assert whether `q` is prime
+/// Uses Fermat's little theorem, thus `q` must be prime. Panics otherwise, use
+/// [mod_inverse_checked] for composite moduli.
 pub fn mod_inverse(a: u64, q: u64) -> u64 {
+    assert!(is_prime(q), "Modulus {q} is not prime");
     mod_exponent(a, q - 2, q)
 }
 
+/// Calculates modular inverse `a^{-1}` of `a` for any modulus `q` using extended GCD. Returns
+/// None if `a` is not invertible, that is if gcd(a, q) != 1.
+///
+/// Intermediate values are i128, thus `q` must be smaller than 2^127. This covers products of
+/// two word sized primes that arise in RNS.
+pub fn mod_inverse_checked(a: u128, q: u128) -> Option<u128> {
+    assert!(
+        q > 1 && q <= i128::MAX as u128,
+        "Modulus {q} must be in (1, 2^127)"
+    );
+
+    let (gcd, x, _) = extended_gcd_i128((a % q) as i128, q as i128);
+    if gcd != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(q as i128) as u128)
+}
+
+/// Calculates modular inverses of all elements of `a` using Montgomery's trick, that is with a
+/// single modular inversion and 3(n-1) multiplications. Returns None if any element is not
+/// invertible.
+///
+/// Modulus `q` may be composite.
+pub fn batch_mod_inverse(a: &[u64], q: u64) -> Option<Vec<u64>> {
+    let mul_mod = mul_mod_routine(q);
+
+    // prefix_i = a_0 * ... * a_i
+    let mut prefix = Vec::with_capacity(a.len());
+    let mut running = 1 % q;
+    for a0 in a.iter() {
+        running = mul_mod(running, a0 % q);
+        prefix.push(running);
+    }
+
+    let mut running_inv = mod_inverse_checked(running as u128, q as u128)? as u64;
+
+    // a_i^{-1} = (a_0 * ... * a_{i-1}) * (a_0 * ... * a_i)^{-1}
+    let mut out = vec![0u64; a.len()];
+    for i in (0..a.len()).rev() {
+        out[i] = if i == 0 {
+            running_inv
+        } else {
+            mul_mod(running_inv, prefix[i - 1])
+        };
+        running_inv = mul_mod(running_inv, a[i] % q);
+    }
+
+    Some(out)
+}
+
 /// Extended GCD algorithm. The funciton calculates the GCD of a & b
 /// and two new variables x & y that satisy ax + by == gcd (i.e. Bezout's identity)
 ///
 /// Refer to attached docs for implementation details
-pub fn extended_gcd(mut a: i64, mut b: i64) -> (i64, i64, i64) {
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    // Bezout coefficients are bounded by max(|a|, |b|), thus fit in i64
+    let (gcd, x, y) = extended_gcd_i128(a as i128, b as i128);
+    (gcd as i64, x as i64, y as i64)
+}
+
+/// Same as [extended_gcd] but for i128 inputs
+pub fn extended_gcd_i128(mut a: i128, mut b: i128) -> (i128, i128, i128) {
     let mut swapped = false;
     if a < b {
         swapped = true;
@@ -130,6 +223,268 @@ pub fn extended_gcd(mut a: i64, mut b: i64) -> (i64, i64, i64) {
     (r1, old_x, old_y)
 }
 
+/// Solves the system x = r_i \mod{m_i} for pairwise coprime moduli `m_i` using Chinese
+/// remainder theorem. Returns x \in [0, \prod m_i), or None if moduli are not pairwise
+/// coprime.
+///
+/// Panics if product of moduli exceeds u128.
+pub fn crt(residues: &[u64], moduli: &[u64]) -> Option<u128> {
+    assert_eq!(residues.len(), moduli.len());
+
+    // x = r_0 + m_0 * t_0 + m_0 * m_1 * t_1 + ...
+    let mut x = 0u128;
+    let mut m = 1u128;
+    for (r, m_i) in residues.iter().zip(moduli.iter()) {
+        assert!(*m_i > 0, "Modulus must be non-zero");
+        let m_i_u128 = *m_i as u128;
+
+        // t = (r - x) * m^{-1} \mod{m_i}
+        let m_inv = if *m_i == 1 {
+            0
+        } else {
+            mod_inverse_checked(m % m_i_u128, m_i_u128)?
+        };
+        let diff = (*r as u128 % m_i_u128 + m_i_u128 - x % m_i_u128) % m_i_u128;
+        let t = (diff * m_inv) % m_i_u128;
+
+        x += m * t;
+        m = m
+            .checked_mul(m_i_u128)
+            .expect("Product of moduli exceeds u128");
+    }
+
+    Some(x)
+}
+
+/// Calculates Jacobi symbol (a/n) for odd n
+///
+/// - [Reference](https://en.wikipedia.org/wiki/Jacobi_symbol#Calculating_the_Jacobi_symbol)
+pub fn jacobi(mut a: u64, mut n: u64) -> i8 {
+    assert!(n & 1 == 1, "Jacobi symbol is undefined for even {n}");
+
+    a %= n;
+    let mut t = 1;
+    while a != 0 {
+        while a & 1 == 0 {
+            a >>= 1;
+            // (2/n) = -1 iff n = 3, 5 \mod{8}
+            if n % 8 == 3 || n % 8 == 5 {
+                t = -t;
+            }
+        }
+        // quadratic reciprocity
+        mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            t = -t;
+        }
+        a %= n;
+    }
+
+    if n == 1 {
+        t
+    } else {
+        0
+    }
+}
+
+/// Calculates square root of `a` modulo odd prime `p` using Tonelli-Shanks. Returns None if `a`
+/// is not a quadratic residue.
+///
+/// Returns the smaller of the two roots `r` and `p - r`.
+///
+/// - [Reference](https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm)
+pub fn mod_sqrt(a: u64, p: u64) -> Option<u64> {
+    assert!(p > 2 && p & 1 == 1, "Modulus {p} is not an odd prime");
+
+    let a = a % p;
+    if a == 0 {
+        return Some(0);
+    }
+    if jacobi(a, p) != 1 {
+        return None;
+    }
+
+    let mul_mod = mul_mod_routine(p);
+
+    // p - 1 = q * 2^s with q odd
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+
+    // z is a quadratic non residue
+    let z = (2..p).find(|z| jacobi(*z, p) == -1).unwrap();
+
+    let mut m = s;
+    let mut c = mod_exponent(z, q, p);
+    let mut t = mod_exponent(a, q, p);
+    let mut r = mod_exponent(a, (q + 1) >> 1, p);
+
+    while t != 1 {
+        // least i s.t. t^{2^i} = 1
+        let mut i = 0;
+        let mut t_pow = t;
+        while t_pow != 1 {
+            t_pow = mul_mod(t_pow, t_pow);
+            i += 1;
+        }
+
+        // b = c^{2^{m - i - 1}}
+        let mut b = c;
+        for _ in 0..(m - i - 1) {
+            b = mul_mod(b, b);
+        }
+
+        m = i;
+        c = mul_mod(b, b);
+        t = mul_mod(t, c);
+        r = mul_mod(r, b);
+    }
+
+    Some(r.min(p - r))
+}
+
+/// Calculates discrete logarithm, that is the smallest x \in [0, bound) s.t. g^x = h \mod{q},
+/// using baby-step giant-step in O(\sqrt{bound}) time and memory. Returns None if no such x
+/// exists or if `g` is not invertible modulo `q`.
+///
+/// Modulus `q` may be composite.
+pub fn discrete_log(g: u64, h: u64, q: u64, bound: u64) -> Option<u64> {
+    assert!(q > 1, "Modulus must be greater than 1");
+
+    let (g, h) = (g % q, h % q);
+    let g_inv = mod_inverse_checked(g as u128, q as u128)? as u64;
+    let mul_mod = mul_mod_routine(q);
+
+    let m = (bound as f64).sqrt().ceil() as u64;
+
+    // baby steps: g^j -> j for j \in [0, m), keeping smallest j
+    let mut baby_steps = HashMap::with_capacity(m as usize);
+    let mut g_j = 1 % q;
+    for j in 0..m {
+        baby_steps.entry(g_j).or_insert(j);
+        g_j = mul_mod(g_j, g);
+    }
+
+    // giant steps: h * g^{-im} for i \in [0, ceil(bound / m))
+    let g_inv_m = mod_exponent(g_inv, m, q);
+    let mut gamma = h;
+    for i in 0..bound.div_ceil(m.max(1)) {
+        if let Some(j) = baby_steps.get(&gamma) {
+            let x = i * m + j;
+            return (x < bound).then_some(x);
+        }
+        gamma = mul_mod(gamma, g_inv_m);
+    }
+
+    None
+}
+
+/// Trial division is performed by all integers below this bound before switching to Pollard's rho
+const TRIAL_DIVISION_BOUND: u64 = 1 << 10;
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns a non-trivial factor of odd composite `n` using Pollard's rho with Floyd's cycle
+/// detection. Pseudo-random map x -> x^2 + c is retried with increasing `c` whenever the cycle
+/// closes without revealing a factor.
+fn pollard_rho(n: u64) -> u64 {
+    let mul_mod = mul_mod_routine(n);
+    for c in 1..n {
+        let f = |x: u64| ((mul_mod(x, x) as u128 + c as u128) % n as u128) as u64;
+        let (mut x, mut y, mut d) = (2, 2, 1);
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd(x.abs_diff(y), n);
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!("{n} is not composite")
+}
+
+/// Returns prime factorization of `n > 0` as pairs (p, e) of distinct primes in increasing order
+/// and their multiplicities.
+///
+/// Uses trial division for small factors and Pollard's rho for the remaining cofactor.
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "Cannot factorize 0");
+
+    let mut primes = vec![];
+    for d in 2..TRIAL_DIVISION_BOUND {
+        if d * d > n {
+            break;
+        }
+        while n.is_multiple_of(d) {
+            primes.push(d);
+            n /= d;
+        }
+    }
+
+    // Remaining cofactor has no factors below `TRIAL_DIVISION_BOUND`
+    let mut composites = vec![];
+    if n > 1 {
+        composites.push(n);
+    }
+    while let Some(m) = composites.pop() {
+        if is_prime(m) {
+            primes.push(m);
+        } else {
+            let d = pollard_rho(m);
+            composites.push(d);
+            composites.push(m / d);
+        }
+    }
+
+    primes.sort_unstable();
+    let mut factors: Vec<(u64, u32)> = vec![];
+    for p in primes {
+        match factors.last_mut() {
+            Some((p_last, e)) if *p_last == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+/// Calculates multiplicative order of `a` modulo `q`, that is the smallest k > 0 s.t.
+/// a^k = 1 \mod{q}. Returns None if `a` is not invertible modulo `q`.
+///
+/// Order divides \phi(q). Starting with k = \phi(q), we strip every prime factor p of k for which
+/// a^{k/p} = 1 still holds. Modulus `q` may be composite.
+pub fn multiplicative_order(a: u64, q: u64) -> Option<u64> {
+    assert!(q > 1, "Modulus must be greater than 1");
+
+    let a = a % q;
+    mod_inverse_checked(a as u128, q as u128)?;
+
+    // \phi(q) = \prod p^{e-1} (p - 1) and its prime factors
+    let mut phi = 1;
+    let mut phi_primes = vec![];
+    for (p, e) in factorize(q) {
+        phi *= p.pow(e - 1) * (p - 1);
+        if e > 1 {
+            phi_primes.push(p);
+        }
+        phi_primes.extend(factorize(p - 1).into_iter().map(|(p, _)| p));
+    }
+    phi_primes.sort_unstable();
+    phi_primes.dedup();
+
+    let mut k = phi;
+    for p in phi_primes {
+        while k.is_multiple_of(p) && mod_exponent(a, k / p, q) == 1 {
+            k /= p;
+        }
+    }
+    Some(k)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,6 +510,248 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mod_inverse_works() {
+        let mut rng = thread_rng();
+        for q in [3329u64, 8380417, 0xffff_ffff_0000_0001] {
+            for _ in 0..100 {
+                let a = rng.gen_range(1..q);
+                let a_inv = mod_inverse(a, q);
+                assert_eq!((a as u128 * a_inv as u128) % q as u128, 1);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mod_inverse_rejects_composite_modulus() {
+        mod_inverse(2, 3329 * 8380417);
+    }
+
+    #[test]
+    fn mod_inverse_checked_works() {
+        // exhaustive for small, possibly composite, moduli
+        for q in 2..200u128 {
+            for a in 0..q {
+                let expected = (1..q).find(|x| (a * x) % q == 1);
+                assert_eq!(mod_inverse_checked(a, q), expected, "{a}^(-1) mod {q}");
+            }
+        }
+
+        // product of two primes
+        let mut rng = thread_rng();
+        let (p0, p1) = (1152921504606748673u128, 1125899906826241u128);
+        let q = p0 * p1;
+        for _ in 0..1000 {
+            let a = rng.gen::<u128>() % q;
+            match mod_inverse_checked(a, q) {
+                Some(a_inv) => assert_eq!(
+                    U256::widening_mul(a, a_inv) % U256::from_parts(0, q),
+                    U256::ONE
+                ),
+                None => assert!(a % p0 == 0 || a % p1 == 0),
+            }
+        }
+        assert_eq!(mod_inverse_checked(p0 * 7, q), None);
+        assert_eq!(mod_inverse_checked(0, q), None);
+    }
+
+    #[test]
+    fn batch_mod_inverse_works() {
+        let mut rng = thread_rng();
+
+        let q = 1152921504606748673u64;
+        let a = (0..1000).map(|_| rng.gen_range(1..q)).collect::<Vec<_>>();
+        let a_inv = batch_mod_inverse(&a, q).unwrap();
+        for (a0, a0_inv) in a.iter().zip(a_inv.iter()) {
+            assert_eq!(*a0_inv, mod_inverse(*a0, q));
+        }
+
+        // composite modulus and inputs larger than modulus
+        let q = 3329u64 * 8380417;
+        let a = (0..1000)
+            .map(|_| rng.gen::<u64>())
+            .filter(|a0| a0 % 3329 != 0 && a0 % 8380417 != 0)
+            .collect::<Vec<_>>();
+        let a_inv = batch_mod_inverse(&a, q).unwrap();
+        for (a0, a0_inv) in a.iter().zip(a_inv.iter()) {
+            assert_eq!(((a0 % q) as u128 * *a0_inv as u128) % q as u128, 1);
+        }
+
+        // non invertible elements
+        assert_eq!(batch_mod_inverse(&[1, 2, 0, 3], 3329), None);
+        assert_eq!(batch_mod_inverse(&[1, 3329 * 5, 2], q), None);
+        assert_eq!(batch_mod_inverse(&[], q), Some(vec![]));
+
+        // even composite modulus, for ex product of RNS primes with a power of two
+        let q = (1u64 << 10) * 3329 * 8380417;
+        let a = (0..1000)
+            .map(|_| rng.gen::<u64>())
+            .filter(|a0| a0 % 2 != 0 && a0 % 3329 != 0 && a0 % 8380417 != 0)
+            .collect::<Vec<_>>();
+        let a_inv = batch_mod_inverse(&a, q).unwrap();
+        for (a0, a0_inv) in a.iter().zip(a_inv.iter()) {
+            assert_eq!(((a0 % q) as u128 * *a0_inv as u128) % q as u128, 1);
+        }
+        assert_eq!(batch_mod_inverse(&[3, 4], q), None);
+    }
+
+    /// Returns a^n \mod{q} by repeated multiplication
+    fn pow_naive(a: u64, n: u64, q: u64) -> u64 {
+        (0..n).fold(1 % q, |acc, _| (acc * a) % q)
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn crt_works() {
+        // exhaustive for small moduli
+        for m0 in 1..24u64 {
+            for m1 in 1..24u64 {
+                for x in 0..m0 * m1 {
+                    let out = crt(&[x % m0, x % m1], &[m0, m1]);
+                    if gcd(m0, m1) == 1 {
+                        assert_eq!(out, Some(x as u128), "x = {x} mod {m0}*{m1}");
+                    } else {
+                        assert_eq!(out, None);
+                    }
+                }
+            }
+        }
+
+        // large moduli
+        let mut rng = thread_rng();
+        let moduli = [1152921504606748673u64, 1125899906826241, 3329];
+        let product = moduli.iter().map(|m| *m as u128).product::<u128>();
+        for _ in 0..1000 {
+            let x = rng.gen::<u128>() % product;
+            let residues = moduli
+                .iter()
+                .map(|m| (x % *m as u128) as u64)
+                .collect::<Vec<_>>();
+            assert_eq!(crt(&residues, &moduli), Some(x));
+        }
+        assert_eq!(crt(&[], &[]), Some(0));
+    }
+
+    #[test]
+    fn jacobi_works() {
+        // Jacobi symbol is product of legendre symbols of prime factors of n, with multiplicity
+        let legendre = |a: u64, p: u64| match pow_naive(a % p, (p - 1) / 2, p) {
+            0 => 0,
+            1 => 1,
+            _ => -1,
+        };
+        for n in (1..300u64).step_by(2) {
+            for a in 0..2 * n {
+                let mut expected = 1;
+                let mut m = n;
+                let mut p = 3;
+                while m > 1 {
+                    while m % p == 0 {
+                        expected *= legendre(a, p);
+                        m /= p;
+                    }
+                    p += 2;
+                }
+                assert_eq!(jacobi(a, n), expected, "({a}/{n})");
+            }
+        }
+    }
+
+    #[test]
+    fn mod_sqrt_works() {
+        let primes = (3..600u64).filter(|p| (2..*p).all(|d| p % d != 0));
+        for p in primes {
+            for a in 0..p {
+                let root = (0..p).find(|x| (x * x) % p == a);
+                let out = mod_sqrt(a, p);
+                match root {
+                    Some(root) => {
+                        let out = out.unwrap();
+                        assert_eq!((out * out) % p, a, "sqrt({a}) mod {p}");
+                        assert_eq!(out, root.min(p - root) % p);
+                    }
+                    None => assert_eq!(out, None, "{a} is not a square mod {p}"),
+                }
+            }
+        }
+
+        // p = 1 mod 2^14
+        let p = 1152921504606748673u64;
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let x = rng.gen::<u64>() % p;
+            let a = ((x as u128 * x as u128) % p as u128) as u64;
+            assert_eq!(mod_sqrt(a, p), Some(x.min(p - x)));
+        }
+    }
+
+    #[test]
+    fn multiplicative_order_works() {
+        for q in 2..150u64 {
+            for a in 0..q {
+                let expected =
+                    (gcd(a, q) == 1).then(|| (1..=q).find(|k| pow_naive(a, *k, q) == 1).unwrap());
+                assert_eq!(multiplicative_order(a, q), expected, "ord({a}) mod {q}");
+            }
+        }
+
+        // 60 bit prime, q - 1 = 2^15 * 2087 * 48193 * 349819
+        let p = 1152921504606748673u64;
+        assert_eq!(multiplicative_order(1, p), Some(1));
+        assert_eq!(multiplicative_order(p - 1, p), Some(2));
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let a = rng.gen_range(1..p);
+            let k = multiplicative_order(a, p).unwrap();
+            assert!((p - 1).is_multiple_of(k));
+            assert_eq!(mod_exponent(a, k, p), 1);
+            for (f, _) in factorize(k) {
+                assert_ne!(mod_exponent(a, k / f, p), 1);
+            }
+        }
+
+        // 2 has order 192 in Goldilocks field
+        let p = 0xffff_ffff_0000_0001u64;
+        assert_eq!(multiplicative_order(2, p), Some(192));
+        assert_eq!(multiplicative_order(p - 1, p), Some(2));
+        assert_eq!(multiplicative_order(7, p), Some(p - 1));
+    }
+
+    #[test]
+    fn discrete_log_works() {
+        for q in 2..60u64 {
+            for g in 0..q {
+                for h in 0..q {
+                    let out = discrete_log(g, h, q, q);
+                    if gcd(g, q) != 1 {
+                        assert_eq!(out, None);
+                        continue;
+                    }
+                    let expected = (0..q).find(|x| pow_naive(g, *x, q) == h);
+                    assert_eq!(out, expected, "log_{g}({h}) mod {q}");
+                }
+            }
+        }
+
+        // small exponent in large field
+        let q = 1152921504606748673u64;
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let g = rng.gen::<u64>() % q;
+            let x = rng.gen::<u64>() % (1 << 20);
+            let h = mod_exponent(g, x, q);
+            assert_eq!(discrete_log(g, h, q, 1 << 20), Some(x));
+        }
+    }
+
     #[test]
     fn fast_modular_inverse_word_size_works() {
         let mut rng = thread_rng();
@@ -176,6 +773,64 @@ mod tests {
                 1,
                 "{a_u32} x {a_u32_inv} (mod 2^32) != 1"
             );
+
+            let a_u128 = rng.gen::<u128>().wrapping_mul(2).wrapping_add(1);
+            let a_u128_inv = u128::fast_inverse(a_u128);
+            assert_eq!(
+                a_u128.wrapping_mul(a_u128_inv),
+                1,
+                "{a_u128} x {a_u128_inv} (mod 2^128) != 1"
+            );
+
+            let a_u256 = U256::from_parts(rng.gen(), rng.gen::<u128>() | 1);
+            let a_u256_inv = U256::fast_inverse(a_u256);
+            assert_eq!(
+                a_u256.wrapping_mul(&a_u256_inv),
+                U256::ONE,
+                "{a_u256} x {a_u256_inv} (mod 2^256) != 1"
+            );
+        }
+    }
+
+    #[test]
+    fn factorize_works() {
+        for n in 1..5000u64 {
+            let factors = factorize(n);
+            assert_eq!(factors.iter().map(|(p, e)| p.pow(*e)).product::<u64>(), n);
+            assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+            assert!(factors.iter().all(|(p, _)| is_prime(*p)));
+        }
+
+        // cofactors beyond trial division
+        let (p0, p1) = (4294967291u64, 4294967279u64);
+        assert_eq!(factorize(p0 * p1), vec![(p1, 1), (p0, 1)]);
+        assert_eq!(
+            factorize(1048573 * 1048573 * 12289),
+            vec![(12289, 1), (1048573, 2)]
+        );
+        assert_eq!(
+            factorize(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1)
+            ]
+        );
+        assert_eq!(
+            factorize(0xffff_ffff_0000_0000),
+            vec![(2, 32), (3, 1), (5, 1), (17, 1), (257, 1), (65537, 1)]
+        );
+
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen::<u64>();
+            let factors = factorize(n);
+            assert_eq!(factors.iter().map(|(p, e)| p.pow(*e)).product::<u64>(), n);
+            assert!(factors.iter().all(|(p, _)| is_prime(*p)));
         }
     }
 }